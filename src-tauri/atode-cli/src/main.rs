@@ -0,0 +1,614 @@
+//! atode - Atode のコマンドライン版
+//!
+//! アプリ本体と同じ`atode.db`を読み書きする（設定は ~/.config/atode/config.json など）。
+//! `--json`を付けると結果をJSONで出力する（シェルスクリプト向け）
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use atode_core::auto_tag::{self, auto_tag_list, TagRules};
+use atode_core::config::{self, Config, CONFIG_FILE};
+use atode_core::resave::SaveResult;
+use atode_core::tags::normalize_tag_name;
+use atode_core::{
+    ArticlePage, ArticleStore, ArticleWithDetails, SaveArticleRequest, SearchFilters, SortKey,
+    SortOrder, SqliteStore, UpdateResult,
+};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "atode",
+    version,
+    about = "後で読む記事をターミナルから管理する"
+)]
+struct Cli {
+    /// 設定ファイル（省略時は ~/.config/atode/config.json など。無ければ既定の設定）
+    #[arg(long, global = true, env = "ATODE_CONFIG", value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// データベースファイル（設定ファイルの`database_path`より優先。無ければ新しく作る）
+    #[arg(long, global = true, env = "ATODE_DB", value_name = "PATH")]
+    db: Option<String>,
+
+    /// 結果をJSONで出力する
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// 記事を保存する（保存済みのURLなら再保存として扱う）
+    Add {
+        url: String,
+        /// タイトル（省略時はURL）
+        #[arg(long)]
+        title: Option<String>,
+        /// タグ（カンマ区切り）
+        #[arg(long, value_name = "TAGS")]
+        tags: Option<String>,
+        /// 自動タグ付けのルールでタグを追加する
+        #[arg(long)]
+        auto_tag: bool,
+    },
+    /// 記事の一覧
+    List {
+        #[command(flatten)]
+        filters: FilterArgs,
+    },
+    /// タイトル・URL・メモのキーワードで検索する
+    Search {
+        #[arg(required = true, num_args = 1..)]
+        text: Vec<String>,
+        #[command(flatten)]
+        filters: FilterArgs,
+    },
+    /// 記事にタグを追加・削除する
+    Tag {
+        id: i64,
+        /// 追加するタグ
+        tags: Vec<String>,
+        /// 削除するタグ
+        #[arg(long, value_name = "TAG")]
+        remove: Vec<String>,
+    },
+    /// 記事をゴミ箱へ移す
+    Rm {
+        #[arg(required = true, num_args = 1..)]
+        ids: Vec<i64>,
+    },
+    /// 記事を既定のブラウザで開く
+    Open { id: i64 },
+    /// 記事を書き出す
+    Export {
+        #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+        #[command(flatten)]
+        filters: FilterArgs,
+    },
+}
+
+// 検索条件（SearchFiltersと同じ項目）
+#[derive(Debug, Args)]
+struct FilterArgs {
+    /// タグ（カンマ区切りはAND）
+    #[arg(long)]
+    tag: Option<String>,
+    /// 検索式（例: "tag:rust -site:qiita"）
+    #[arg(long)]
+    query: Option<String>,
+    #[arg(long)]
+    site: Option<String>,
+    /// 読書ステータス（inbox / reading / done / reference など）
+    #[arg(long)]
+    status: Option<String>,
+    /// 登録日時の範囲（日付・RFC 3339・"last 7 days" などの相対指定）
+    #[arg(long, value_name = "WHEN")]
+    created_after: Option<String>,
+    #[arg(long, value_name = "WHEN")]
+    created_before: Option<String>,
+    /// 更新日時の範囲
+    #[arg(long, value_name = "WHEN")]
+    updated_after: Option<String>,
+    #[arg(long, value_name = "WHEN")]
+    updated_before: Option<String>,
+    /// タグ検索で子孫タグの記事も含める
+    #[arg(long)]
+    descendants: bool,
+    /// 並び順（created / updated / title / site / random / relevance）
+    #[arg(long, value_parser = parse_sort_key)]
+    sort: Option<SortKey>,
+    /// 昇順・降順（asc / desc）
+    #[arg(long, value_parser = parse_sort_order)]
+    order: Option<SortOrder>,
+    /// --sort random の乱数シード
+    #[arg(long)]
+    seed: Option<u32>,
+    #[arg(long)]
+    limit: Option<u32>,
+    #[arg(long)]
+    offset: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    Json,
+    Csv,
+}
+
+// 並び順はアプリ本体（JSON）と同じ名前で指定する
+fn parse_sort_key(value: &str) -> Result<SortKey, String> {
+    serde_json::from_value(value.into()).map_err(|_| format!("不明な並び順です: {value}"))
+}
+
+fn parse_sort_order(value: &str) -> Result<SortOrder, String> {
+    serde_json::from_value(value.into())
+        .map_err(|_| format!("asc か desc を指定してください: {value}"))
+}
+
+impl FilterArgs {
+    fn into_filters(self, text: Option<String>) -> SearchFilters {
+        SearchFilters {
+            tag_query: self.tag,
+            query: self.query,
+            site: self.site,
+            text,
+            status: self.status,
+            created_after: self.created_after,
+            created_before: self.created_before,
+            updated_after: self.updated_after,
+            updated_before: self.updated_before,
+            include_descendants: self.descendants.then_some(true),
+            sort: self.sort,
+            order: self.order,
+            seed: self.seed,
+            limit: self.limit,
+            offset: self.offset,
+        }
+    }
+}
+
+// `rm`の結果（--json）
+#[derive(Debug, Serialize)]
+struct Deleted {
+    deleted: Vec<i64>,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    // 標準出力は結果だけにする（保存・統合などの経過ログは出さない）
+    atode_core::set_logging(false);
+
+    let result =
+        open_store(cli.config.as_deref(), cli.db.as_deref()).and_then(|(mut store, tag_rules)| {
+            run(
+                cli.command,
+                cli.json,
+                &mut store,
+                &tag_rules,
+                &mut io::stdout().lock(),
+            )
+        });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("エラー: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// 設定ファイルの既定の置き場所（Linux: ~/.config/atode、Windows: %APPDATA%\atode）
+fn default_config_dir() -> Result<PathBuf, String> {
+    dirs::config_dir()
+        .map(|dir| dir.join("atode"))
+        .ok_or_else(|| "設定ディレクトリが分かりません。--config で指定してください".to_string())
+}
+
+// 設定ファイルを読み、記事データベースと自動タグ付けのルールファイルの場所を決める
+//
+// 設定ファイルは --config（ATODE_CONFIG）か既定の置き場所の config.json。
+// 設定内の相対パスは設定ファイルのあるディレクトリを基準にする。
+// 誤って空のデータベースを作らないよう、新しく作るのは --db（ATODE_DB）を指定したときだけ
+fn open_store(config: Option<&Path>, db: Option<&str>) -> Result<(SqliteStore, PathBuf), String> {
+    let (config, base_dir) = load_config(config)?;
+    let tag_rules = base_dir.join(&config.tag_rules_path);
+    let database_path = if let Some(db) = db {
+        db.to_string()
+    } else {
+        let path = base_dir.join(&config.database_path);
+        if !path.exists() {
+            return Err(format!(
+                "データベースが見つかりません ({})。新しく作る場合は --db で指定してください",
+                path.display()
+            ));
+        }
+        path.to_string_lossy().into_owned()
+    };
+    let store = SqliteStore::open(&database_path, config.url_normalization, config.resave)?;
+    Ok((store, tag_rules))
+}
+
+// 指定された設定ファイルは必須。省略時は既定の置き場所に config.json があれば読む。
+// 設定と、相対パスの基準になるディレクトリを返す
+fn load_config(path: Option<&Path>) -> Result<(Config, PathBuf), String> {
+    let path = if let Some(path) = path {
+        path.to_path_buf()
+    } else {
+        let dir = default_config_dir()?;
+        let path = dir.join(CONFIG_FILE);
+        if !path.exists() {
+            return Ok((Config::default(), dir));
+        }
+        path
+    };
+    let config = Config::read(&path)
+        .map_err(|e| format!("設定ファイルを読み込めません ({}): {e}", path.display()))?;
+    let base_dir = path.parent().map_or_else(PathBuf::new, Path::to_path_buf);
+    Ok((config, base_dir))
+}
+
+fn run(
+    command: Command,
+    json: bool,
+    store: &mut dyn ArticleStore,
+    tag_rules_path: &Path,
+    out: &mut dyn Write,
+) -> Result<(), String> {
+    match command {
+        Command::Add {
+            url,
+            title,
+            tags,
+            auto_tag,
+        } => {
+            let result = add_article(store, url, title, tags.as_deref(), auto_tag, tag_rules_path)?;
+            if json {
+                write_json(out, &result)
+            } else {
+                write_line(
+                    out,
+                    &format!("{} (ID: {})", result.message, result.article_id),
+                )
+            }
+        }
+        Command::List { filters } => {
+            let page = store.search_articles(&filters.into_filters(None))?;
+            write_page(out, &page, json)
+        }
+        Command::Search { text, filters } => {
+            let page = store.search_articles(&filters.into_filters(Some(text.join(" "))))?;
+            write_page(out, &page, json)
+        }
+        Command::Tag { id, tags, remove } => {
+            let article = tag_article(store, id, tags, &remove)?;
+            if json {
+                write_json(out, &article)
+            } else {
+                write_line(out, &format!("🏷️ {id}: {}", article.tags.join(", ")))
+            }
+        }
+        Command::Rm { mut ids } => {
+            // 同じIDの重複は1つにまとめ、すべてを1トランザクションで移す
+            // （存在しないIDがあれば、どれも移さない）
+            let mut seen = Vec::with_capacity(ids.len());
+            ids.retain(|id| {
+                let first = !seen.contains(id);
+                seen.push(*id);
+                first
+            });
+            store.delete_articles(&ids)?;
+            if json {
+                write_json(out, &Deleted { deleted: ids })
+            } else {
+                let ids: Vec<String> = ids.iter().map(i64::to_string).collect();
+                write_line(out, &format!("🗑️ ゴミ箱へ移しました: {}", ids.join(", ")))
+            }
+        }
+        Command::Open { id } => {
+            let article = find_article(store, id)?;
+            config::open_url(&article.url)?;
+            if json {
+                write_json(out, &article)
+            } else {
+                write_line(out, &article.url)
+            }
+        }
+        Command::Export { format, filters } => {
+            let page = store.search_articles(&filters.into_filters(None))?;
+            match format {
+                ExportFormat::Json => write_json(out, &page.articles),
+                ExportFormat::Csv => write_csv(out, &page.articles),
+            }
+        }
+    }
+}
+
+// タグ指定（カンマ区切り）に、--auto-tag なら自動タグ付けのタグを加えて保存する
+fn add_article(
+    store: &mut dyn ArticleStore,
+    url: String,
+    title: Option<String>,
+    tags: Option<&str>,
+    auto_tag: bool,
+    tag_rules_path: &Path,
+) -> Result<SaveResult, String> {
+    let title = title.unwrap_or_else(|| url.clone());
+    let mut tags: Vec<String> = tags
+        .unwrap_or("")
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect();
+    if auto_tag {
+        let rules = TagRules::load(tag_rules_path).or_else(|e| {
+            eprintln!("⚠️ {e} - 既定のルールを使用");
+            TagRules::parse(auto_tag::DEFAULT_RULES)
+        })?;
+        for tag in auto_tag_list(&rules, &url, &title).0 {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+    }
+    store.save_article(SaveArticleRequest {
+        url,
+        title,
+        tags: Some(tags.join(",")),
+    })
+}
+
+// 今のタグから`remove`を除き`tags`を加える（URL・タイトルはそのまま）
+fn tag_article(
+    store: &mut dyn ArticleStore,
+    id: i64,
+    tags: Vec<String>,
+    remove: &[String],
+) -> Result<ArticleWithDetails, String> {
+    let article = find_article(store, id)?;
+    let remove: Vec<String> = remove.iter().map(|tag| normalize_tag_name(tag)).collect();
+    let mut new_tags: Vec<String> = article
+        .tags
+        .into_iter()
+        .filter(|tag| !remove.contains(&normalize_tag_name(tag)))
+        .collect();
+    new_tags.extend(tags);
+    let request = SaveArticleRequest {
+        url: article.url,
+        title: article.title,
+        tags: Some(new_tags.join(",")),
+    };
+    // URLはそのままだが、保存後にURL正規化のルールが変わると別の記事と重なることがある
+    match store.update_article(id, &request, false)? {
+        UpdateResult::Updated | UpdateResult::Merged { .. } => find_article(store, id),
+        UpdateResult::Conflict { article } => Err(format!(
+            "URLが別の記事と重なっているため変更できません (ID: {}, {})。アプリで統合してください",
+            article.id, article.url
+        )),
+    }
+}
+
+fn find_article(store: &dyn ArticleStore, id: i64) -> Result<ArticleWithDetails, String> {
+    store
+        .get_article(id)?
+        .ok_or_else(|| format!("記事が見つかりません (ID: {id})"))
+}
+
+// `atode list | head`などで出力先が先に閉じても、エラーにしない
+fn write_line(out: &mut dyn Write, line: &str) -> Result<(), String> {
+    match writeln!(out, "{line}") {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => result.map_err(|e| e.to_string()),
+    }
+}
+
+fn write_json<T: Serialize + ?Sized>(out: &mut dyn Write, value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    write_line(out, &json)
+}
+
+// 1記事1行（ID・ステータス・タイトル・タグ・URL）
+fn write_page(out: &mut dyn Write, page: &ArticlePage, json: bool) -> Result<(), String> {
+    if json {
+        return write_json(out, page);
+    }
+    for article in &page.articles {
+        let tags = if article.tags.is_empty() {
+            String::new()
+        } else {
+            format!("  [{}]", article.tags.join(", "))
+        };
+        write_line(
+            out,
+            &format!(
+                "{:>5}  {:<9}  {}{tags}\n       {}",
+                article.id,
+                article.status.as_deref().unwrap_or("-"),
+                article.title,
+                article.url
+            ),
+        )?;
+    }
+    let shown = page.articles.len();
+    if u32::try_from(shown).is_ok_and(|shown| shown < page.total) {
+        write_line(out, &format!("（{}件中 {}件を表示）", page.total, shown))
+    } else {
+        write_line(out, &format!("（{}件）", page.total))
+    }
+}
+
+const CSV_HEADER: &str = "id,url,title,site,tags,status,notes,created_at,updated_at";
+
+fn write_csv(out: &mut dyn Write, articles: &[ArticleWithDetails]) -> Result<(), String> {
+    write_line(out, CSV_HEADER)?;
+    for article in articles {
+        let fields = [
+            article.id.to_string(),
+            article.url.clone(),
+            article.title.clone(),
+            article.site_name.clone().unwrap_or_default(),
+            article.tags.join(","),
+            article.status.clone().unwrap_or_default(),
+            article.notes.clone(),
+            article.created_at.to_rfc3339(),
+            article.updated_at.to_rfc3339(),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        write_line(out, &row.join(","))?;
+    }
+    Ok(())
+}
+
+// カンマ・引用符・改行を含む値は引用符で囲む（RFC 4180）
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atode_core::MemoryStore;
+    use std::path::Path;
+
+    // コマンドラインを解釈してメモリ上の記事に対して実行し、出力を返す
+    fn atode(store: &mut MemoryStore, args: &[&str]) -> Result<String, String> {
+        let cli = Cli::try_parse_from(std::iter::once("atode").chain(args.iter().copied()))
+            .map_err(|e| e.to_string())?;
+        let mut out = Vec::new();
+        run(
+            cli.command,
+            cli.json,
+            store,
+            Path::new("does-not-exist.json"),
+            &mut out,
+        )?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    fn json(store: &mut MemoryStore, args: &[&str]) -> serde_json::Value {
+        serde_json::from_str(&atode(store, args).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_add_list_search_and_tag() {
+        let mut store = MemoryStore::default();
+        let added = json(
+            &mut store,
+            &[
+                "add",
+                "https://example.com/rust",
+                "--title",
+                "Rust async",
+                "--tags",
+                "rust, async",
+                "--json",
+            ],
+        );
+        assert_eq!(added["status"], "created");
+        let id = added["article_id"].as_i64().unwrap();
+        atode(
+            &mut store,
+            &[
+                "add",
+                "https://example.com/python",
+                "--title",
+                "Python tips",
+            ],
+        )
+        .unwrap();
+
+        let page = json(&mut store, &["list", "--tag", "rust", "--json"]);
+        assert_eq!(page["total"], 1);
+        assert_eq!(page["articles"][0]["title"], "Rust async");
+
+        let page = json(&mut store, &["search", "python", "--json"]);
+        assert_eq!(page["articles"][0]["url"], "https://example.com/python");
+
+        let page = json(
+            &mut store,
+            &["list", "--sort", "title", "--order", "asc", "--json"],
+        );
+        assert_eq!(page["articles"][0]["title"], "Python tips");
+
+        let id_arg = id.to_string();
+        let article = json(
+            &mut store,
+            &["tag", &id_arg, "tokio", "--remove", "Async", "--json"],
+        );
+        assert_eq!(article["tags"], serde_json::json!(["rust", "tokio"]));
+
+        let text = atode(&mut store, &["list"]).unwrap();
+        assert!(text.contains("Rust async  [rust, tokio]"));
+        assert!(text.contains("（2件）"));
+
+        assert!(atode(&mut store, &["list", "--sort", "newest"]).is_err());
+    }
+
+    #[test]
+    fn test_rm_and_export() {
+        let mut store = MemoryStore::default();
+        atode(
+            &mut store,
+            &[
+                "add",
+                "https://example.com/a",
+                "--title",
+                "Hello, \"world\"",
+            ],
+        )
+        .unwrap();
+        atode(
+            &mut store,
+            &["add", "https://example.com/b", "--tags", "x,y"],
+        )
+        .unwrap();
+
+        let csv = atode(&mut store, &["export", "--format", "csv"]).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(lines.len(), 3);
+        assert!(csv.contains(r#""Hello, ""world""""#));
+        assert!(csv.contains(r#","x,y","#));
+
+        // 存在しないIDを含むと何も消さない
+        assert!(atode(&mut store, &["rm", "1", "99"]).is_err());
+        let deleted = json(&mut store, &["rm", "1", "1", "--json"]);
+        assert_eq!(deleted["deleted"], serde_json::json!([1]));
+        assert!(atode(&mut store, &["open", "1"]).is_err());
+
+        let exported = json(&mut store, &["export"]);
+        assert_eq!(exported.as_array().unwrap().len(), 1);
+        assert_eq!(exported[0]["url"], "https://example.com/b");
+    }
+
+    #[test]
+    fn test_database_is_not_created_implicitly() {
+        let dir = std::env::temp_dir().join(format!("atode-cli-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config = dir.join("config.json");
+        std::fs::write(&config, r#"{"database_path": "atode.db"}"#).unwrap();
+
+        // 設定の相対パスは設定ファイルの場所が基準。無ければ作らずにエラー
+        let error = open_store(Some(&config), None).err().unwrap();
+        assert!(error.contains("データベースが見つかりません"));
+        assert!(!dir.join("atode.db").exists());
+
+        let db = dir.join("atode.db");
+        let (_, tag_rules) = open_store(Some(&config), Some(db.to_str().unwrap())).unwrap();
+        assert_eq!(tag_rules, dir.join("tag_rules.json"));
+        assert!(open_store(Some(&config), None).is_ok());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
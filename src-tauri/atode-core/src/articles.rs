@@ -0,0 +1,1535 @@
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Result};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::canonical::{self, DuplicateArticle};
+use crate::resave::{ResavePolicy, SaveResult, TagPolicy, TitlePolicy};
+use crate::url_rules::UrlRules;
+use crate::{dates, public_suffix, query, search, sites, tags};
+
+// フロントエンド用（結果）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleWithDetails {
+    pub id: i64,
+    pub url: String,
+    pub title: String,
+    pub site_name: Option<String>,
+    pub tags: Vec<String>,
+    pub status: Option<String>,
+    // 日時はRFC 3339（UTC）で返す
+    pub status_changed_at: Option<DateTime<Utc>>,
+    pub notes: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    // キーワード検索時の一致箇所（search::HIGHLIGHT_START/ENDで囲まれる）
+    pub snippet: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchFilters {
+    pub tag_query: Option<String>,
+    // 検索式（AND / OR / NOT・括弧・field:value。query.rs参照）
+    pub query: Option<String>,
+    pub site: Option<String>,
+    // タイトル・URL・メモのキーワード検索（日本語は文字の並びで部分一致）
+    pub text: Option<String>,
+    // 読書ステータス
+    pub status: Option<String>,
+    // 登録日時・更新日時の範囲（after以降、beforeより前）
+    // RFC 3339・日付・相対指定（today, last 7 days, 3 days ago など）はローカルタイムで解釈する
+    pub created_after: Option<String>,
+    pub created_before: Option<String>,
+    pub updated_after: Option<String>,
+    pub updated_before: Option<String>,
+    // タグ検索で子孫タグの記事も含める（programming → rust, python など）
+    pub include_descendants: Option<bool>,
+    // 並び順（未指定なら更新日時の新しい順、キーワード検索時は関連度順）
+    pub sort: Option<SortKey>,
+    pub order: Option<SortOrder>,
+    // sort = random のときの乱数シード（同じシードなら同じ順序）
+    pub seed: Option<u32>,
+    // ページング（未指定なら全件）
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortKey {
+    Created,
+    Updated,
+    Title,
+    Site,
+    Random,
+    Relevance,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+// 記事検索の結果（1ページ分と、条件に一致する全件数）
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArticlePage {
+    pub articles: Vec<ArticleWithDetails>,
+    pub total: u32,
+    pub offset: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveArticleRequest {
+    pub url: String,
+    pub title: String,
+    pub tags: Option<String>,
+}
+
+// 記事編集の結果
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum UpdateResult {
+    Updated,
+    /// 変更後のURLが別の記事と同じ（merge: true で再実行すると統合する）
+    Conflict {
+        article: DuplicateArticle,
+    },
+    /// 別の記事（`merged_id`）を統合した
+    Merged {
+        merged_id: i64,
+    },
+}
+
+// "よく使う"タグ管理用
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: u32,
+}
+
+// 記事の登録・再保存（再保存の扱いは`resave`の設定に従う。メモやステータスは保持する）
+// ゴミ箱内の記事を再保存した場合はゴミ箱から戻す
+#[allow(clippy::needless_pass_by_value)]
+pub fn store_article(
+    db: &Connection,
+    url_rules: &UrlRules,
+    resave: &ResavePolicy,
+    request: SaveArticleRequest,
+) -> Result<SaveResult, String> {
+    let normalized_url = url_rules.normalize(&request.url);
+    let canonical_url = canonical::canonical_url(&normalized_url);
+    let parsed_url = Url::parse(&normalized_url).map_err(|e| e.to_string())?;
+    let host = parsed_url.host_str().unwrap_or("");
+    let site = public_suffix::site_identity(host);
+
+    // ph.1 サイトID確定
+    let site_id = get_or_create_site(db, host, &site.name)?;
+
+    // ph.2 保存するタグの特定（別名は正式なタグにする）
+    let requested_tags = resolve_tags(db, request.tags.as_deref())?;
+
+    // ph.3 既存記事をチェック（URLの表記が違っても正規形が同じなら同じ記事）
+    let existing_article = db
+        .query_row(
+            "SELECT id, title, deleted_at IS NOT NULL
+            FROM articles
+            WHERE canonical_url = ?1 OR url = ?2
+            ORDER BY id
+            LIMIT 1",
+            params![canonical_url, normalized_url],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, bool>(2)?,
+                ))
+            },
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    let result = if let Some((article_id, title, deleted)) = existing_article {
+        let title_changed = resave.title == TitlePolicy::Replace && title != request.title;
+        db.execute(
+            "UPDATE articles
+             SET title = ?, deleted_at = NULL, updated_at = CURRENT_TIMESTAMP
+             WHERE id = ?",
+            params![
+                if title_changed {
+                    &request.title
+                } else {
+                    &title
+                },
+                article_id
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+
+        // ph.4 タグの差分を反映
+        let current_tags = article_tag_ids(db, article_id)?;
+        let mut removed_tags = Vec::new();
+        if resave.tags == TagPolicy::Replace {
+            for (tag_id, tag_name) in &current_tags {
+                if !requested_tags.iter().any(|(id, _)| id == tag_id) {
+                    db.execute(
+                        "DELETE FROM article_tags WHERE article_id = ? AND tag_id = ?",
+                        params![article_id, tag_id],
+                    )
+                    .map_err(|e| e.to_string())?;
+                    removed_tags.push(tag_name.clone());
+                }
+            }
+        }
+        let added_tags = add_article_tags(db, article_id, &requested_tags)?;
+
+        SaveResult::resaved(article_id, added_tags, removed_tags, title_changed, deleted)
+    } else {
+        // 新規記事を作成
+        log!("記事保存開始：{}", request.url);
+        db.execute(
+            "INSERT INTO articles (url, canonical_url, title, site_id, created_at, updated_at) 
+             VALUES (?, ?, ?, ?, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)",
+            params![normalized_url, canonical_url, request.title, site_id],
+        )
+        .map_err(|e| e.to_string())?;
+
+        let article_id = db.last_insert_rowid();
+        log!("記事作成完了: {} (ID: {})", request.title, article_id);
+        let added_tags = add_article_tags(db, article_id, &requested_tags)?;
+        SaveResult::created(article_id, added_tags)
+    };
+
+    log!("記事保存完了: {result}");
+    Ok(result)
+}
+
+// 記事の編集（タグは指定したものに置き換える）
+// URLは保存時と同じく正規化し、正規形が別の記事と同じなら`merge`のときだけその記事を統合する
+pub fn edit_article(
+    db: &Connection,
+    url_rules: &UrlRules,
+    article_id: i64,
+    request: &SaveArticleRequest,
+    merge: bool,
+) -> Result<UpdateResult, String> {
+    let exists = db
+        .query_row("SELECT 1 FROM articles WHERE id = ?", [article_id], |_| {
+            Ok(())
+        })
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if exists.is_none() {
+        return Err(format!("記事が見つかりません (ID: {article_id})"));
+    }
+
+    let normalized_url = url_rules.normalize(request.url.trim());
+    let canonical_url = canonical::canonical_url(&normalized_url);
+    let parsed_url = Url::parse(&normalized_url).map_err(|e| e.to_string())?;
+    let host = parsed_url.host_str().unwrap_or("");
+
+    // ph.1 URLが重なる記事の確認（統合しないなら何も変えずに返す）
+    let conflict = db
+        .query_row(
+            "SELECT id, url, title, created_at, deleted_at IS NOT NULL
+            FROM articles
+            WHERE id != ?1
+              AND (canonical_url = ?2 OR url = ?3)
+            ORDER BY id
+            LIMIT 1",
+            params![article_id, canonical_url, normalized_url],
+            |row| {
+                Ok(DuplicateArticle {
+                    id: row.get(0)?,
+                    url: row.get(1)?,
+                    title: row.get(2)?,
+                    created_at: timestamp_from_row(3, &row.get::<_, String>(3)?)?,
+                    deleted: row.get(4)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if !merge {
+        if let Some(article) = conflict {
+            log!("URLが重複: ID {} (統合の確認待ち)", article.id);
+            return Ok(UpdateResult::Conflict { article });
+        }
+    }
+
+    // ph.2 タグを置き換える
+    let requested_tags = resolve_tags(db, request.tags.as_deref())?;
+    db.execute(
+        "DELETE FROM article_tags WHERE article_id = ?",
+        [article_id],
+    )
+    .map_err(|e| e.to_string())?;
+    add_article_tags(db, article_id, &requested_tags)?;
+
+    // ph.3 重なる記事を統合（タグ・メモ・ステータス履歴を引き継ぐ）
+    let merged_id = match conflict {
+        Some(other) => {
+            canonical::merge_articles(db, other.id, article_id)?;
+            Some(other.id)
+        }
+        None => None,
+    };
+
+    // ph.4 記事の基本情報を更新
+    let site_id = get_or_create_site(db, host, &public_suffix::site_identity(host).name)?;
+    db.execute(
+        "UPDATE articles
+         SET title = ?, url = ?, canonical_url = ?, site_id = ?, updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?",
+        params![
+            request.title,
+            normalized_url,
+            canonical_url,
+            site_id,
+            article_id
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    log!("記事編集完了: ID {article_id}");
+    Ok(
+        merged_id.map_or(UpdateResult::Updated, |merged_id| UpdateResult::Merged {
+            merged_id,
+        }),
+    )
+}
+
+// 保存するタグの特定（名前を正規化し、別名は正式なタグにする。重複は除く）
+fn resolve_tags(db: &Connection, tags: Option<&str>) -> Result<Vec<(i64, String)>, String> {
+    let mut resolved: Vec<(i64, String)> = Vec::new();
+    for tag_name in tags.unwrap_or("").split(',') {
+        let tag_name = tags::normalize_tag_name(tag_name);
+        if tag_name.is_empty() {
+            continue;
+        }
+        let tag_id = get_or_create_tag(db, &tag_name)?;
+        if !resolved.iter().any(|(id, _)| *id == tag_id) {
+            resolved.push((tag_id, tag_name_by_id(db, tag_id)?));
+        }
+    }
+    Ok(resolved)
+}
+
+// 記事に付いていないタグを付け、付けたタグ名を返す
+fn add_article_tags(
+    db: &Connection,
+    article_id: i64,
+    tags: &[(i64, String)],
+) -> Result<Vec<String>, String> {
+    let mut added = Vec::new();
+    for (tag_id, tag_name) in tags {
+        let rows = db
+            .execute(
+                "INSERT OR IGNORE INTO article_tags (article_id, tag_id) VALUES (?, ?)",
+                params![article_id, tag_id],
+            )
+            .map_err(|e| e.to_string())?;
+        if rows > 0 {
+            added.push(tag_name.clone());
+        }
+    }
+    Ok(added)
+}
+
+// 記事に付いているタグ（ID順）
+fn article_tag_ids(db: &Connection, article_id: i64) -> Result<Vec<(i64, String)>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT t.id, t.name
+            FROM article_tags at
+            JOIN tags t
+              ON at.tag_id = t.id
+            WHERE at.article_id = ?
+            ORDER BY t.id",
+        )
+        .map_err(|e| e.to_string())?;
+    let tags = stmt
+        .query_map([article_id], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    Ok(tags)
+}
+
+fn tag_name_by_id(db: &Connection, tag_id: i64) -> Result<String, String> {
+    db.query_row("SELECT name FROM tags WHERE id = ?", [tag_id], |row| {
+        row.get(0)
+    })
+    .map_err(|e| e.to_string())
+}
+
+// 条件に一致する記事の件数
+pub fn count_articles(db: &Connection, filters: &SearchFilters) -> Result<u32, String> {
+    let query = build_article_query(db, filters)?;
+    db.query_row(
+        &query.count_sql,
+        rusqlite::params_from_iter(&query.params),
+        |row| row.get(0),
+    )
+    .map_err(|e| e.to_string())
+}
+
+// 記事検索の本体（キーワード指定時は既定で全文検索の関連度順）
+pub fn query_articles(db: &Connection, filters: &SearchFilters) -> Result<ArticlePage, String> {
+    let query = build_article_query(db, filters)?;
+    let param_refs: Vec<&dyn rusqlite::ToSql> = query
+        .params
+        .iter()
+        .map(|p| p as &dyn rusqlite::ToSql)
+        .collect();
+
+    // 件数（ページングに関係なく条件に一致する全件）
+    let total: u32 = db
+        .query_row(&query.count_sql, &param_refs[..], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+
+    let mut stmt = db.prepare(&query.page_sql).map_err(|e| e.to_string())?;
+    let articles = stmt
+        .query_map(&param_refs[..], article_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut result = Vec::new();
+    for article in articles {
+        let mut article = article.map_err(|e| e.to_string())?;
+        if let Some(keywords) = &query.keywords {
+            article.snippet = search::snippet(
+                &article.title,
+                &article.url,
+                &article.notes,
+                &keywords.terms,
+            );
+        }
+        result.push(article);
+    }
+
+    Ok(ArticlePage {
+        articles: result,
+        total,
+        offset: filters.offset.unwrap_or(0),
+    })
+}
+
+// 記事1件分の列（`article_from_row`の並び。記事は`a`、サイトは`s`、ステータスは`st`）
+const ARTICLE_COLUMNS: &str = "
+        a.id,
+        a.url,
+        a.title,
+        COALESCE(s.name, '') as site_name,
+        (SELECT GROUP_CONCAT(t.name)
+           FROM article_tags at
+           JOIN tags t ON at.tag_id = t.id
+          WHERE at.article_id = a.id) as tags,
+        a.created_at,
+        a.updated_at,
+        st.name as status,
+        a.status_changed_at,
+        a.notes";
+
+// IDで記事を取得（ゴミ箱内の記事は`None`）
+pub fn get_article(db: &Connection, article_id: i64) -> Result<Option<ArticleWithDetails>, String> {
+    db.query_row(
+        &format!(
+            "SELECT {ARTICLE_COLUMNS}
+            FROM articles a
+            LEFT JOIN sites s ON a.site_id = s.id
+            LEFT JOIN statuses st ON a.status_id = st.id
+            WHERE a.id = ? AND a.deleted_at IS NULL"
+        ),
+        [article_id],
+        article_from_row,
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+// 人気タグ（ステータス指定時はそのステータスの記事のみで集計）
+pub fn popular_tags(
+    db: &Connection,
+    limit: usize,
+    status: Option<&str>,
+) -> Result<Vec<TagCount>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT
+           TRIM(t.name) as tag_name
+         , COUNT(*) as count
+        FROM tags t
+        JOIN article_tags at
+          ON t.id = at.tag_id
+        JOIN articles a
+          ON at.article_id = a.id
+        LEFT JOIN statuses st
+          ON a.status_id = st.id
+        WHERE t.name != ?3
+          AND a.deleted_at IS NULL
+          AND (?1 IS NULL OR st.name = ?1 COLLATE NOCASE)
+        GROUP BY TRIM(t.name) 
+        ORDER BY count DESC, t.name ASC
+        LIMIT ?2",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let tag_counts = stmt
+        .query_map(
+            params![status.map(str::trim), limit, tags::EXCLUDED_FROM_STATS],
+            |row| {
+                Ok(TagCount {
+                    tag: row.get(0)?,
+                    count: row.get(1)?,
+                })
+            },
+        )
+        .map_err(|e| e.to_string())?;
+
+    let mut result = Vec::new();
+    for tag_count in tag_counts {
+        result.push(tag_count.map_err(|e| e.to_string())?);
+    }
+
+    Ok(result)
+}
+
+// 記事検索のSQL（件数用と1ページ分）と、共通のパラメータ
+struct ArticleQuery {
+    count_sql: String,
+    page_sql: String,
+    params: Vec<String>,
+    keywords: Option<search::KeywordQuery>,
+}
+
+fn build_article_query(db: &Connection, filters: &SearchFilters) -> Result<ArticleQuery, String> {
+    let mut params: Vec<String> = Vec::new();
+
+    // キーワード検索の一致記事
+    let keywords = filters.text.as_deref().and_then(search::parse_keywords);
+    let (match_cte, match_join) = keywords.as_ref().map_or_else(
+        || (String::new(), ""),
+        |keywords| {
+            (
+                search::match_cte(keywords, &mut params),
+                "JOIN m ON m.article_id = a.id",
+            )
+        },
+    );
+
+    let conditions = article_conditions(db, filters, &mut params)?;
+    let from_where = format!(
+        "FROM articles a
+     {match_join}
+     LEFT JOIN sites s ON a.site_id = s.id
+     LEFT JOIN statuses st ON a.status_id = st.id
+     WHERE {}",
+        conditions.join(" AND ")
+    );
+
+    // タグはGROUP BYせず記事ごとに集約する（並び順のインデックスをそのまま使えるように）
+    let limit = filters
+        .limit
+        .map_or_else(|| "-1".to_string(), |limit| limit.to_string());
+    let offset = filters.offset.unwrap_or(0);
+    let page_sql = format!(
+        "{match_cte}SELECT {ARTICLE_COLUMNS}
+     {from_where}
+     ORDER BY {}
+     LIMIT {limit} OFFSET {offset}",
+        order_clause(filters, keywords.is_some())
+    );
+
+    Ok(ArticleQuery {
+        count_sql: format!("{match_cte}SELECT COUNT(*) {from_where}"),
+        page_sql,
+        params,
+        keywords,
+    })
+}
+
+// 検索条件（WHERE句）の組み立て
+fn article_conditions(
+    db: &Connection,
+    filters: &SearchFilters,
+    params: &mut Vec<String>,
+) -> Result<Vec<String>, String> {
+    // ゴミ箱内の記事は除外
+    let mut conditions = vec!["a.deleted_at IS NULL".to_string()];
+
+    // フィルター処理：サイト
+    if let Some(site) = &filters.site {
+        conditions.push("s.name LIKE ?".to_string());
+        params.push(format!("%{site}%"));
+    }
+    // フィルター処理：タグ（指定したタグがすべて付いている記事）
+    if let Some(tag_query) = &filters.tag_query {
+        let include_descendants = filters.include_descendants.unwrap_or(false);
+        for tag in tag_query.split(',').map(str::trim) {
+            let tag_ids = if include_descendants {
+                tags::SUBTREE_SQL
+            } else {
+                "SELECT id FROM tags WHERE name = ?"
+            };
+            conditions.push(format!(
+                "a.id IN (SELECT at.article_id FROM article_tags at WHERE at.tag_id IN ({tag_ids}))"
+            ));
+            params.push(tags::canonical_name(db, tag)?);
+        }
+    }
+    // フィルター処理：読書ステータス
+    if let Some(status) = &filters.status {
+        conditions.push("st.name = ? COLLATE NOCASE".to_string());
+        params.push(status.trim().to_string());
+    }
+    // フィルター処理：登録日時・更新日時の範囲
+    for (value, condition) in [
+        (&filters.created_after, "a.created_at >= ?"),
+        (&filters.created_before, "a.created_at < ?"),
+        (&filters.updated_after, "a.updated_at >= ?"),
+        (&filters.updated_before, "a.updated_at < ?"),
+    ] {
+        if let Some(value) = value.as_deref().filter(|v| !v.trim().is_empty()) {
+            conditions.push(condition.to_string());
+            params.push(dates::to_db_timestamp(dates::resolve(value)?));
+        }
+    }
+    // フィルター処理：検索式
+    if let Some(expr) = filters
+        .query
+        .as_deref()
+        .map(query::parse)
+        .transpose()
+        .map_err(|e| e.to_string())?
+        .flatten()
+    {
+        let include_descendants = filters.include_descendants.unwrap_or(false);
+        conditions.push(expr.to_sql(db, include_descendants, params)?);
+    }
+
+    Ok(conditions)
+}
+
+// シード付きランダム順のキー（(id + seed) mod P に掛けて mod P）。
+// 積がi64に収まるよう、乗数は P より十分小さくする（溢れるとREALになり全行が同じキーになる）
+pub(crate) const RANDOM_MODULUS: u64 = 4_294_967_291;
+pub(crate) const RANDOM_MULTIPLIER: u64 = 1_103_515_245;
+
+// 並び順（ORDER BY句）。ページ送りで順序が揺れないよう最後にIDで並べる
+fn order_clause(filters: &SearchFilters, has_keywords: bool) -> String {
+    let (sort, order) = resolve_sort(filters, has_keywords);
+    let dir = match order {
+        SortOrder::Asc => "ASC",
+        SortOrder::Desc => "DESC",
+    };
+
+    match sort {
+        SortKey::Created => format!("a.created_at {dir}, a.id {dir}"),
+        SortKey::Updated => format!("a.updated_at {dir}, a.id {dir}"),
+        SortKey::Title => format!("a.title COLLATE NOCASE {dir}, a.id {dir}"),
+        SortKey::Site => format!("s.name COLLATE NOCASE {dir}, a.updated_at DESC, a.id DESC"),
+        // シードが同じなら同じ順序になるので、ページを送っても重複・欠落しない
+        SortKey::Random => filters.seed.map_or_else(
+            || "RANDOM()".to_string(),
+            |seed| {
+                let seed = u64::from(seed) % RANDOM_MODULUS;
+                format!(
+                    "((a.id + {seed}) % {RANDOM_MODULUS}) * {RANDOM_MULTIPLIER} % {RANDOM_MODULUS}, a.id"
+                )
+            },
+        ),
+        // bm25は小さいほど関連度が高い
+        SortKey::Relevance if has_keywords => {
+            "m.rank ASC, a.updated_at DESC, a.id DESC".to_string()
+        }
+        SortKey::Relevance => "a.updated_at DESC, a.id DESC".to_string(),
+    }
+}
+
+// 並び順の既定値の適用（キーワード検索時は関連度順、それ以外は更新日時順。
+// 日時は新しい順、名前は昇順が既定）
+pub(crate) fn resolve_sort(filters: &SearchFilters, has_keywords: bool) -> (SortKey, SortOrder) {
+    let sort = filters.sort.unwrap_or(if has_keywords {
+        SortKey::Relevance
+    } else {
+        SortKey::Updated
+    });
+    let default_order = match sort {
+        SortKey::Title | SortKey::Site => SortOrder::Asc,
+        _ => SortOrder::Desc,
+    };
+    (sort, filters.order.unwrap_or(default_order))
+}
+
+// DBの日時文字列を読む（読めなければ行の変換エラー）
+pub(crate) fn timestamp_from_row(index: usize, value: &str) -> Result<DateTime<Utc>> {
+    dates::parse_db_timestamp(value).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, e.into())
+    })
+}
+
+// 検索結果の1行を記事データへ変換
+fn article_from_row(row: &rusqlite::Row) -> Result<ArticleWithDetails> {
+    let tags_str: Option<String> = row.get(4)?;
+    let tags = tags_str.map_or_else(Vec::new, |tags_str| {
+        tags_str
+            .split(',')
+            .map(|tag| tag.trim().to_string())
+            .collect()
+    });
+    Ok(ArticleWithDetails {
+        id: row.get(0)?,
+        url: row.get(1)?,
+        title: row.get(2)?,
+        site_name: Some(row.get(3)?),
+        tags,
+        status: row.get(7)?,
+        status_changed_at: row
+            .get::<_, Option<String>>(8)?
+            .map(|value| timestamp_from_row(8, &value))
+            .transpose()?,
+        notes: row.get(9)?,
+        created_at: timestamp_from_row(5, &row.get::<_, String>(5)?)?,
+        updated_at: timestamp_from_row(6, &row.get::<_, String>(6)?)?,
+        snippet: None,
+    })
+}
+
+// 登録サイトIDの特定（ホスト名に別名が登録されていれば参照先のサイトを使う）
+pub fn get_or_create_site(db: &Connection, host: &str, site_name: &str) -> Result<i64, String> {
+    // 登録済みサイトの検索（重複確認）
+    let site_id_opt = match sites::find_site_id(db, host)? {
+        Some(site_id) => Some(site_id),
+        None => sites::find_site_id(db, site_name)?,
+    };
+    if let Some(site_id) = site_id_opt {
+        log!("既存サイト使用: {site_name} (ID: {site_id})");
+        Ok(site_id)
+    } else {
+        // 新しいサイトを作成（INSERT）
+        db.execute("INSERT INTO sites (name) VALUES (?)", params![site_name])
+            .map_err(|e| e.to_string())?;
+
+        // 作成したサイトのIDを取得
+        let site_id = db.last_insert_rowid();
+        log!("新規サイト作成: {site_name} (ID: {site_id})");
+        Ok(site_id)
+    }
+}
+
+// 登録に使うタグの特定（名前は正規化し、別名なら正式なタグを使う）
+pub fn get_or_create_tag(db: &Connection, tag_name: &str) -> Result<i64, String> {
+    let tag_name = tags::normalize_tag_name(tag_name);
+    if tag_name.is_empty() {
+        return Err("タグ名が空です".to_string());
+    }
+
+    if let Some(tag_id) = tags::find_tag_id(db, &tag_name)? {
+        log!("既存タグ使用: {tag_name} (ID: {tag_id})");
+        Ok(tag_id)
+    } else {
+        // 新しいタグを作成
+        db.execute("INSERT INTO tags (name) VALUES (?)", [&tag_name])
+            .map_err(|e| e.to_string())?;
+
+        let tag_id = db.last_insert_rowid();
+        log!("新規タグ作成: {tag_name} (ID: {tag_id})");
+        Ok(tag_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resave::SaveStatus;
+    use crate::{init_database, notes, status, trash};
+
+    // 記事IDをurlから求める
+    fn get_article_id_by_url(db: &Connection, url: &str) -> Result<i64, String> {
+        let mut stmt = db
+            .prepare("SELECT id FROM articles WHERE url = ?")
+            .map_err(|e| e.to_string())?;
+
+        stmt.query_row([url], |row| row.get(0))
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_url_normalization() {
+        let rules = UrlRules::default();
+        // URL正規化テスト（通常）
+        assert_eq!(
+            rules.normalize("https://example.com/page?ref=123"),
+            "https://example.com/page"
+        );
+        assert_eq!(
+            rules.normalize("https://github.com/user/repo"),
+            "https://github.com/user/repo"
+        );
+
+        // 特別扱い（GETパラメータを殺さない）ケース（config.json の url_normalization）
+        assert_eq!(
+            rules.normalize("https://www.youtube.com/watch?v=a1b2c3d4e5"),
+            "https://www.youtube.com/watch?v=a1b2c3d4e5"
+        );
+
+        // ローカルファイル
+        assert_eq!(
+            rules.normalize("file:///C:/Users/test/document.html"),
+            "file:///C:/Users/test/document.html"
+        );
+    }
+
+    #[test]
+    fn test_keyword_search() {
+        let db = init_database(":memory:").unwrap();
+        for (url, title) in [
+            ("https://example.com/a", "Rust async book"),
+            ("https://example.com/b", "Python tips"),
+            ("https://rust-lang.org/learn", "Learn"),
+        ] {
+            db.execute(
+                "INSERT INTO articles (url, title) VALUES (?, ?)",
+                params![url, title],
+            )
+            .unwrap();
+        }
+
+        let filters = SearchFilters {
+            text: Some("rust".to_string()),
+            ..Default::default()
+        };
+        let result = query_articles(&db, &filters).unwrap().articles;
+        let mut urls: Vec<&str> = result.iter().map(|a| a.url.as_str()).collect();
+        urls.sort_unstable();
+        assert_eq!(
+            urls,
+            ["https://example.com/a", "https://rust-lang.org/learn"]
+        );
+
+        let snippet = result
+            .iter()
+            .find(|a| a.url == "https://example.com/a")
+            .and_then(|a| a.snippet.clone())
+            .unwrap();
+        assert_eq!(snippet, "\u{2}Rust\u{3} async book");
+
+        // タイトル更新がインデックスへ反映される
+        db.execute(
+            "UPDATE articles SET title = 'Go tips' WHERE url = 'https://example.com/a'",
+            [],
+        )
+        .unwrap();
+        let filters = SearchFilters {
+            text: Some("async".to_string()),
+            ..Default::default()
+        };
+        assert!(query_articles(&db, &filters).unwrap().articles.is_empty());
+    }
+
+    #[test]
+    fn test_japanese_keyword_search() {
+        let db = init_database(":memory:").unwrap();
+        for (url, title) in [
+            ("https://qiita.com/a", "Rustの所有権を図解で理解する"),
+            ("https://zenn.dev/b", "東京で働くエンジニアの一日"),
+        ] {
+            db.execute(
+                "INSERT INTO articles (url, title) VALUES (?, ?)",
+                params![url, title],
+            )
+            .unwrap();
+        }
+
+        let search = |text: &str| {
+            let filters = SearchFilters {
+                text: Some(text.to_string()),
+                ..Default::default()
+            };
+            query_articles(&db, &filters)
+                .unwrap()
+                .articles
+                .into_iter()
+                .map(|a| a.url)
+                .collect::<Vec<_>>()
+        };
+
+        // 3文字以上は索引、2文字以下はLIKEで照合
+        assert_eq!(search("所有権"), ["https://qiita.com/a"]);
+        assert_eq!(search("東京"), ["https://zenn.dev/b"]);
+        assert_eq!(search("図解 所有権"), ["https://qiita.com/a"]);
+        assert!(search("図解 東京").is_empty());
+    }
+
+    #[test]
+    fn test_resave_keeps_notes() {
+        let db = init_database(":memory:").unwrap();
+        let request = |title: &str| SaveArticleRequest {
+            url: "https://example.com/page".to_string(),
+            title: title.to_string(),
+            tags: Some("example".to_string()),
+        };
+
+        // タイトルを置き換える設定でも、メモは保持する
+        let resave = ResavePolicy {
+            title: TitlePolicy::Replace,
+            ..Default::default()
+        };
+        let save = |title: &str| {
+            store_article(&db, &UrlRules::default(), &resave, request(title))
+                .unwrap()
+                .status
+        };
+
+        assert_eq!(save("Before"), SaveStatus::Created);
+        let article_id = get_article_id_by_url(&db, "https://example.com/page").unwrap();
+        notes::set_notes(&db, article_id, "tokioのselect!を試す").unwrap();
+
+        assert_eq!(save("After"), SaveStatus::Updated);
+        assert_eq!(
+            notes::get_notes(&db, article_id).unwrap(),
+            "tokioのselect!を試す"
+        );
+
+        // メモもキーワード検索の対象
+        let filters = SearchFilters {
+            text: Some("select!".to_string()),
+            ..Default::default()
+        };
+        let result = query_articles(&db, &filters).unwrap().articles;
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].title, "After");
+        assert_eq!(
+            result[0].snippet.as_deref(),
+            Some("tokioの\u{2}select!\u{3}を試す")
+        );
+    }
+
+    #[test]
+    fn test_status_filter() {
+        let db = init_database(":memory:").unwrap();
+        for url in ["https://example.com/a", "https://example.com/b"] {
+            db.execute(
+                "INSERT INTO articles (url, title) VALUES (?, 'title')",
+                [url],
+            )
+            .unwrap();
+        }
+        status::set_article_status(&db, 2, "reading").unwrap();
+
+        let filters = SearchFilters {
+            status: Some("reading".to_string()),
+            ..Default::default()
+        };
+        let result = query_articles(&db, &filters).unwrap().articles;
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].url, "https://example.com/b");
+        assert_eq!(result[0].status.as_deref(), Some("reading"));
+    }
+
+    #[test]
+    fn test_trashed_articles_are_hidden() {
+        let db = init_database(":memory:").unwrap();
+        let request = || SaveArticleRequest {
+            url: "https://example.com/page".to_string(),
+            title: "Trashed".to_string(),
+            tags: Some("example".to_string()),
+        };
+        store_article(
+            &db,
+            &UrlRules::default(),
+            &ResavePolicy::default(),
+            request(),
+        )
+        .unwrap();
+        trash::move_to_trash(
+            &db,
+            get_article_id_by_url(&db, "https://example.com/page").unwrap(),
+        )
+        .unwrap();
+
+        assert!(query_articles(&db, &SearchFilters::default())
+            .unwrap()
+            .articles
+            .is_empty());
+        let filters = SearchFilters {
+            text: Some("Trashed".to_string()),
+            ..Default::default()
+        };
+        assert!(query_articles(&db, &filters).unwrap().articles.is_empty());
+
+        // 同じURLを保存し直すとゴミ箱から戻る
+        let result = store_article(
+            &db,
+            &UrlRules::default(),
+            &ResavePolicy::default(),
+            request(),
+        )
+        .unwrap();
+        assert_eq!(result.status, SaveStatus::Updated);
+        assert!(result.restored);
+        assert!(trash::list_trash(&db).unwrap().is_empty());
+        assert_eq!(
+            query_articles(&db, &SearchFilters::default())
+                .unwrap()
+                .articles
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_tag_search_with_descendants() {
+        let db = init_database(":memory:").unwrap();
+        for (url, tags) in [
+            ("https://example.com/rust", "rust"),
+            ("https://example.com/python", "python"),
+            ("https://example.com/cooking", "cooking"),
+        ] {
+            let request = SaveArticleRequest {
+                url: url.to_string(),
+                title: "title".to_string(),
+                tags: Some(tags.to_string()),
+            };
+            store_article(&db, &UrlRules::default(), &ResavePolicy::default(), request).unwrap();
+        }
+        get_or_create_tag(&db, "programming").unwrap();
+        tags::set_tag_parent(&db, "rust", Some("programming")).unwrap();
+        tags::set_tag_parent(&db, "python", Some("programming")).unwrap();
+
+        let search = |include_descendants| {
+            let filters = SearchFilters {
+                tag_query: Some("programming".to_string()),
+                include_descendants: Some(include_descendants),
+                ..Default::default()
+            };
+            let mut urls: Vec<String> = query_articles(&db, &filters)
+                .unwrap()
+                .articles
+                .into_iter()
+                .map(|a| a.url)
+                .collect();
+            urls.sort();
+            urls
+        };
+
+        assert!(search(false).is_empty());
+        assert_eq!(
+            search(true),
+            ["https://example.com/python", "https://example.com/rust"]
+        );
+    }
+
+    #[test]
+    fn test_tags_are_normalized_on_save() {
+        let db = init_database(":memory:").unwrap();
+        get_or_create_tag(&db, "javascript").unwrap();
+        tags::add_alias(&db, "js", "javascript").unwrap();
+
+        let request = SaveArticleRequest {
+            url: "https://example.com/page".to_string(),
+            title: "title".to_string(),
+            tags: Some("JS, JavaScript ,ＴｙｐｅＳｃｒｉｐｔ".to_string()),
+        };
+        store_article(&db, &UrlRules::default(), &ResavePolicy::default(), request).unwrap();
+
+        let result = query_articles(&db, &SearchFilters::default())
+            .unwrap()
+            .articles;
+        let mut tags = result[0].tags.clone();
+        tags.sort();
+        assert_eq!(tags, ["javascript", "typescript"]);
+
+        // 別名でも検索できる
+        let filters = SearchFilters {
+            tag_query: Some("js".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(query_articles(&db, &filters).unwrap().articles.len(), 1);
+    }
+
+    #[test]
+    fn test_save_from_aliased_host() {
+        let db = init_database(":memory:").unwrap();
+        let save = |url: &str| {
+            let request = SaveArticleRequest {
+                url: url.to_string(),
+                title: "title".to_string(),
+                tags: None,
+            };
+            store_article(&db, &UrlRules::default(), &ResavePolicy::default(), request).unwrap();
+        };
+
+        save("https://www.youtube.com/watch?v=1");
+        sites::add_alias(&db, "m.youtube.com", "youtube.com").unwrap();
+        save("https://m.youtube.com/watch?v=2");
+
+        let sites = sites::list_sites(&db).unwrap();
+        assert_eq!(sites.len(), 1);
+        assert_eq!(sites[0].name, "youtube.com");
+        assert_eq!(sites[0].article_count, 2);
+    }
+
+    #[test]
+    fn test_resave_policy() {
+        let db = init_database(":memory:").unwrap();
+        let request = |title: &str, tags: &str| SaveArticleRequest {
+            url: "https://example.com/page".to_string(),
+            title: title.to_string(),
+            tags: Some(tags.to_string()),
+        };
+        let tags = || {
+            let article_id = get_article_id_by_url(&db, "https://example.com/page").unwrap();
+            article_tag_ids(&db, article_id)
+                .unwrap()
+                .into_iter()
+                .map(|(_, name)| name)
+                .collect::<Vec<_>>()
+        };
+
+        let merge = ResavePolicy::default();
+        let result = store_article(
+            &db,
+            &UrlRules::default(),
+            &merge,
+            request("Edited", "example, rust"),
+        )
+        .unwrap();
+        assert_eq!(result.status, SaveStatus::Created);
+        assert_eq!(result.added_tags, ["example", "rust"]);
+
+        // 既定: タグは追加のみ、タイトルはそのまま
+        let result = store_article(
+            &db,
+            &UrlRules::default(),
+            &merge,
+            request("Page title", "example, Async"),
+        )
+        .unwrap();
+        assert_eq!(result.status, SaveStatus::Updated);
+        assert_eq!(result.added_tags, ["async"]);
+        assert!(!result.title_changed);
+        assert_eq!(result.message, "保存済みの記事です（タグを追加: async）");
+        assert_eq!(tags(), ["example", "rust", "async"]);
+
+        let result = store_article(
+            &db,
+            &UrlRules::default(),
+            &merge,
+            request("Page title", "example"),
+        )
+        .unwrap();
+        assert_eq!(result.status, SaveStatus::Unchanged);
+        let title: String = db
+            .query_row("SELECT title FROM articles", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(title, "Edited");
+
+        // 置き換え: 以前の動作
+        let replace = ResavePolicy {
+            tags: TagPolicy::Replace,
+            title: TitlePolicy::Replace,
+        };
+        let result = store_article(
+            &db,
+            &UrlRules::default(),
+            &replace,
+            request("Page title", "example, go"),
+        )
+        .unwrap();
+        assert_eq!(result.added_tags, ["go"]);
+        assert_eq!(result.removed_tags, ["rust", "async"]);
+        assert!(result.title_changed);
+        assert_eq!(tags(), ["example", "go"]);
+    }
+
+    #[test]
+    fn test_edit_article_by_id() {
+        let db = init_database(":memory:").unwrap();
+        let request = |url: &str, tags: &str| SaveArticleRequest {
+            url: url.to_string(),
+            title: "title".to_string(),
+            tags: Some(tags.to_string()),
+        };
+        for (url, tags) in [
+            ("https://example.com/a", "rust"),
+            ("https://zenn.dev/b", "async"),
+        ] {
+            store_article(
+                &db,
+                &UrlRules::default(),
+                &ResavePolicy::default(),
+                request(url, tags),
+            )
+            .unwrap();
+        }
+        notes::set_notes(&db, 2, "memo").unwrap();
+        let rules = UrlRules::default();
+
+        // URLを変えても同じ記事を更新する（URLは正規化し、サイトも付け替える）
+        let result = edit_article(
+            &db,
+            &rules,
+            1,
+            &request("https://qiita.com/a?utm_source=x", "rust"),
+            false,
+        )
+        .unwrap();
+        assert!(matches!(result, UpdateResult::Updated));
+        assert_eq!(
+            get_article_id_by_url(&db, "https://qiita.com/a").unwrap(),
+            1
+        );
+        let site: String = db
+            .query_row(
+                "SELECT s.name FROM articles a JOIN sites s ON a.site_id = s.id WHERE a.id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(site, "qiita.com");
+
+        // 別の記事と重なるURLは、統合の確認を返して何も変えない
+        let collide = request("http://zenn.dev/b/", "go");
+        let result = edit_article(&db, &rules, 1, &collide, false).unwrap();
+        let UpdateResult::Conflict { article } = result else {
+            panic!("conflict expected: {result:?}");
+        };
+        assert_eq!(article.id, 2);
+        assert_eq!(
+            query_articles(&db, &SearchFilters::default())
+                .unwrap()
+                .articles
+                .len(),
+            2
+        );
+
+        let result = edit_article(&db, &rules, 1, &collide, true).unwrap();
+        assert!(matches!(result, UpdateResult::Merged { merged_id: 2 }));
+        let articles = query_articles(&db, &SearchFilters::default())
+            .unwrap()
+            .articles;
+        assert_eq!(articles.len(), 1);
+        assert_eq!(articles[0].url, "http://zenn.dev/b/");
+        assert_eq!(articles[0].tags, ["async", "go"]);
+        assert_eq!(articles[0].notes, "memo");
+
+        assert!(edit_article(&db, &rules, 99, &collide, false).is_err());
+    }
+
+    #[test]
+    fn test_resave_url_variant() {
+        let db = init_database(":memory:").unwrap();
+        let save = |url: &str| {
+            let request = SaveArticleRequest {
+                url: url.to_string(),
+                title: "title".to_string(),
+                tags: None,
+            };
+            store_article(&db, &UrlRules::default(), &ResavePolicy::default(), request)
+                .unwrap()
+                .status
+        };
+
+        assert_eq!(
+            save("https://www.youtube.com/watch?v=abc123"),
+            SaveStatus::Created
+        );
+        assert_eq!(save("https://youtu.be/abc123?t=42"), SaveStatus::Unchanged);
+        assert_eq!(save("http://example.com/post/"), SaveStatus::Created);
+        assert_eq!(
+            save("https://example.com/post/index.html"),
+            SaveStatus::Unchanged
+        );
+
+        // 最初に保存したURLのまま
+        let urls: Vec<String> = query_articles(&db, &SearchFilters::default())
+            .unwrap()
+            .articles
+            .into_iter()
+            .map(|article| article.url)
+            .collect();
+        assert_eq!(urls.len(), 2);
+        assert!(urls.contains(&"http://example.com/post/".to_string()));
+    }
+
+    #[test]
+    fn test_site_name_is_registrable_domain() {
+        let db = init_database(":memory:").unwrap();
+        for url in [
+            "https://news.example.co.uk/a",
+            "https://www.example.co.uk/b",
+            "https://foo.github.io/c",
+            "https://bar.github.io/d",
+        ] {
+            let request = SaveArticleRequest {
+                url: url.to_string(),
+                title: "title".to_string(),
+                tags: None,
+            };
+            store_article(&db, &UrlRules::default(), &ResavePolicy::default(), request).unwrap();
+        }
+
+        let mut sites: Vec<(String, u32)> = sites::list_sites(&db)
+            .unwrap()
+            .into_iter()
+            .map(|site| (site.name, site.article_count))
+            .collect();
+        sites.sort();
+        assert_eq!(
+            sites,
+            [
+                ("bar.github.io".to_string(), 1),
+                ("example.co.uk".to_string(), 2),
+                ("foo.github.io".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_paging_and_sort() {
+        let db = init_database(":memory:").unwrap();
+        for (i, title) in ["Charlie", "alpha", "Bravo", "delta", "Echo"]
+            .iter()
+            .enumerate()
+        {
+            db.execute(
+                "INSERT INTO articles (url, title, created_at, updated_at)
+                 VALUES (?, ?, ?, datetime('2025-01-01', ?))",
+                params![
+                    format!("https://example.com/{i}"),
+                    title,
+                    format!("2025-01-0{} 00:00:00", 5 - i),
+                    format!("+{i} days")
+                ],
+            )
+            .unwrap();
+        }
+
+        let titles = |filters: SearchFilters| {
+            let page = query_articles(&db, &filters).unwrap();
+            let titles: Vec<String> = page.articles.into_iter().map(|a| a.title).collect();
+            (titles, page.total)
+        };
+
+        // 既定は更新日時の新しい順
+        let page = SearchFilters {
+            limit: Some(2),
+            offset: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(titles(page), (vec!["delta".into(), "Bravo".into()], 5));
+
+        let by_title = SearchFilters {
+            sort: Some(SortKey::Title),
+            limit: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(titles(by_title).0, ["alpha", "Bravo", "Charlie"]);
+
+        let by_created = SearchFilters {
+            sort: Some(SortKey::Created),
+            order: Some(SortOrder::Asc),
+            limit: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(titles(by_created).0, ["Echo"]);
+
+        // 同じシードならページを分けても全件が重複なく返る
+        let random = |offset| SearchFilters {
+            sort: Some(SortKey::Random),
+            seed: Some(42),
+            limit: Some(3),
+            offset: Some(offset),
+            ..Default::default()
+        };
+        let mut all = titles(random(0)).0;
+        all.extend(titles(random(3)).0);
+        all.sort();
+        assert_eq!(all, ["Bravo", "Charlie", "Echo", "alpha", "delta"]);
+
+        // シードが大きくても並びが崩れない（ID順にならない）
+        let shuffled = titles(SearchFilters {
+            sort: Some(SortKey::Random),
+            seed: Some(u32::MAX),
+            ..Default::default()
+        })
+        .0;
+        let by_id = titles(SearchFilters {
+            sort: Some(SortKey::Created),
+            order: Some(SortOrder::Asc),
+            ..Default::default()
+        })
+        .0;
+        assert_eq!(shuffled.len(), 5);
+        assert_ne!(shuffled, by_id);
+    }
+
+    #[test]
+    fn test_boolean_query() {
+        let db = init_database(":memory:").unwrap();
+        for (url, title, tags) in [
+            ("https://zenn.dev/a", "Error handling in Rust", "rust,async"),
+            (
+                "https://qiita.com/b",
+                "Error handling with tokio",
+                "rust,tokio",
+            ),
+            ("https://zenn.dev/c", "Error handling basics", "rust"),
+            ("https://zenn.dev/d", "Error handling in Go", "go,async"),
+        ] {
+            let request = SaveArticleRequest {
+                url: url.to_string(),
+                title: title.to_string(),
+                tags: Some(tags.to_string()),
+            };
+            store_article(&db, &UrlRules::default(), &ResavePolicy::default(), request).unwrap();
+        }
+        db.execute(
+            "UPDATE articles SET created_at = '2025-12-31 12:00:00' WHERE url = 'https://zenn.dev/c'",
+            [],
+        )
+        .unwrap();
+
+        let search = |query: &str| {
+            let filters = SearchFilters {
+                query: Some(query.to_string()),
+                ..Default::default()
+            };
+            query_articles(&db, &filters).map(|page| {
+                let mut urls: Vec<String> = page.articles.into_iter().map(|a| a.url).collect();
+                urls.sort();
+                urls
+            })
+        };
+
+        assert_eq!(
+            search(r#"tag:rust (tag:async OR tag:tokio) -site:qiita title:"error handling""#)
+                .unwrap(),
+            ["https://zenn.dev/a"]
+        );
+        assert_eq!(
+            search("tag:rust saved:<2026-01-01").unwrap(),
+            ["https://zenn.dev/c"]
+        );
+        assert_eq!(search("Go OR tokio").unwrap().len(), 2);
+
+        // 解析エラーは位置付きで返る
+        let err = search("tag:rust (tag:async").unwrap_err();
+        assert!(err.contains("10文字目"), "{err}");
+    }
+
+    #[test]
+    fn test_date_range_filters() {
+        let db = init_database(":memory:").unwrap();
+        for url_and_created_at in [
+            ["https://example.com/old", "2025-06-01 09:00:00"],
+            ["https://example.com/new", "2026-03-01 09:00:00"],
+        ] {
+            db.execute(
+                "INSERT INTO articles (url, title, created_at, updated_at) VALUES (?1, 'title', ?2, ?2)",
+                url_and_created_at,
+            )
+            .unwrap();
+        }
+
+        let search = |filters: SearchFilters| {
+            query_articles(&db, &filters)
+                .map(|page| page.articles.into_iter().map(|a| a.url).collect::<Vec<_>>())
+        };
+
+        let result = search(SearchFilters {
+            created_after: Some("2026-01-01T00:00:00Z".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(result, ["https://example.com/new"]);
+
+        let result = search(SearchFilters {
+            updated_before: Some("2026-01-01T00:00:00+09:00".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(result, ["https://example.com/old"]);
+
+        // 相対指定は現在時刻から解釈する（どちらの記事も7日より前に登録。
+        // 相対指定の解釈そのものは dates のテストで現在時刻を固定して確かめている）
+        let result = search(SearchFilters {
+            created_after: Some("last 7 days".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(result.is_empty());
+
+        assert!(search(SearchFilters {
+            created_after: Some("someday".to_string()),
+            ..Default::default()
+        })
+        .is_err());
+
+        // 日時はRFC 3339で返す
+        let filters = SearchFilters {
+            created_before: Some("2026-01-01".to_string()),
+            ..Default::default()
+        };
+        let article = &query_articles(&db, &filters).unwrap().articles[0];
+        let json = serde_json::to_value(article).unwrap();
+        assert_eq!(json["created_at"], "2025-06-01T09:00:00Z");
+    }
+
+    #[test]
+    fn test_article_queries_use_indexes() {
+        let db = init_database(":memory:").unwrap();
+        let plan = |filters: SearchFilters| {
+            let query = build_article_query(&db, &filters).unwrap();
+            let mut plan = Vec::new();
+            for sql in [&query.count_sql, &query.page_sql] {
+                let mut stmt = db.prepare(&format!("EXPLAIN QUERY PLAN {sql}")).unwrap();
+                let rows = stmt
+                    .query_map(rusqlite::params_from_iter(&query.params), |row| {
+                        row.get::<_, String>(3)
+                    })
+                    .unwrap();
+                plan.extend(rows.map(Result::unwrap));
+            }
+            plan.join("\n")
+        };
+
+        // 日時順の一覧はインデックス順に読むだけで、並び替え用の一時B木を作らない
+        for sort in [SortKey::Updated, SortKey::Created] {
+            let result = plan(SearchFilters {
+                sort: Some(sort),
+                limit: Some(50),
+                ..Default::default()
+            });
+            assert!(!result.contains("TEMP B-TREE"), "{result}");
+            assert!(
+                result.contains("idx_articles_updated_at")
+                    || result.contains("idx_articles_created_at"),
+                "{result}"
+            );
+        }
+
+        // タグ絞り込みはタグ側から記事を引く
+        let result = plan(SearchFilters {
+            tag_query: Some("rust".to_string()),
+            ..Default::default()
+        });
+        assert!(result.contains("idx_article_tags_tag_id"), "{result}");
+
+        let result = plan(SearchFilters {
+            tag_query: Some("rust".to_string()),
+            include_descendants: Some(true),
+            ..Default::default()
+        });
+        assert!(result.contains("idx_article_tags_tag_id"), "{result}");
+
+        // サイトからの逆引き
+        let mut stmt = db
+            .prepare("EXPLAIN QUERY PLAN SELECT COUNT(*) FROM articles WHERE site_id = ?")
+            .unwrap();
+        let detail: String = stmt.query_row([1], |row| row.get(3)).unwrap();
+        assert!(detail.contains("idx_articles_site_id"), "{detail}");
+    }
+}
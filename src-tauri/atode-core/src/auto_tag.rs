@@ -0,0 +1,372 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use url::Url;
+
+use crate::{public_suffix, tags};
+
+/// 同梱の既定ルール（ルールファイルが無いときに使う）
+pub const DEFAULT_RULES: &str = include_str!("../../tag_rules.json");
+
+/// 自動タグ付けのルール（読み込み時に検証・コンパイル済み）
+#[derive(Debug, Default)]
+pub struct TagRules {
+    rules: Vec<Rule>,
+}
+
+/// `preview_auto_tags`の結果
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AutoTagPreview {
+    pub tags: Vec<String>,
+    /// 一致したルールの名前（評価順）
+    pub matched_rules: Vec<String>,
+}
+
+// ルールファイルの形式
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleFile {
+    rules: Vec<RuleConfig>,
+}
+
+// 条件はすべて満たしたときに一致（host・pathはいずれかに一致すればよい）
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleConfig {
+    name: Option<String>,
+    // ホスト名のglob。ワイルドカードが無ければサブドメインも一致（github.com → gist.github.com）
+    #[serde(default)]
+    host: Vec<String>,
+    // パスのglob（`*`は任意の文字列、`?`は任意の1文字）
+    #[serde(default)]
+    path: Vec<String>,
+    url_regex: Option<String>,
+    title_regex: Option<String>,
+    // それまでに付いたタグにすべて含まれていること
+    #[serde(default)]
+    has_tags: Vec<String>,
+    #[serde(default)]
+    add: Vec<String>,
+    #[serde(default)]
+    remove: Vec<String>,
+}
+
+#[derive(Debug)]
+struct Rule {
+    name: String,
+    hosts: Vec<Regex>,
+    paths: Vec<Regex>,
+    url: Option<Regex>,
+    title: Option<Regex>,
+    has_tags: Vec<String>,
+    add: Vec<String>,
+    remove: Vec<String>,
+}
+
+impl TagRules {
+    /// ルールファイルを読み込む（無ければ同梱の既定ルール）
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            log!(
+                "ℹ️ タグルールファイルが見つかりません ({}) - 既定のルールを使用",
+                path.display()
+            );
+            return Self::parse(DEFAULT_RULES);
+        }
+
+        let content = fs::read_to_string(path).map_err(|e| {
+            format!(
+                "タグルールファイルを読み込めません ({}): {e}",
+                path.display()
+            )
+        })?;
+        let rules = Self::parse(&content)?;
+        log!(
+            "✅ タグルール読み込み成功: {}件 ({})",
+            rules.len(),
+            path.display()
+        );
+        Ok(rules)
+    }
+
+    /// ルール（JSON）を検証してコンパイルする
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let file: RuleFile = serde_json::from_str(content)
+            .map_err(|e| format!("タグルールの形式が正しくありません: {e}"))?;
+
+        let rules = file
+            .rules
+            .into_iter()
+            .enumerate()
+            .map(|(index, config)| Rule::compile(index, config))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { rules })
+    }
+
+    pub const fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// ルールを順に評価し、タグを追加・削除する。一致したルール名を返す
+    pub fn apply(&self, url: &str, title: &str, tags: &mut Vec<String>) -> Vec<String> {
+        let parsed = Url::parse(url).ok();
+        let host = parsed
+            .as_ref()
+            .and_then(Url::host_str)
+            .unwrap_or("")
+            .to_lowercase();
+        let path = parsed.as_ref().map_or("", Url::path);
+
+        let mut matched = Vec::new();
+        for rule in &self.rules {
+            if !rule.matches(&host, path, url, title, tags) {
+                continue;
+            }
+            for tag in &rule.add {
+                if !tags.contains(tag) {
+                    tags.push(tag.clone());
+                }
+            }
+            tags.retain(|tag| !rule.remove.contains(tag));
+            matched.push(rule.name.clone());
+        }
+        matched
+    }
+}
+
+impl Rule {
+    fn compile(index: usize, config: RuleConfig) -> Result<Self, String> {
+        let name = config
+            .name
+            .unwrap_or_else(|| format!("ルール{}", index + 1));
+        let invalid = |message: String| format!("タグルール「{name}」: {message}");
+
+        let normalize = |tags: Vec<String>| -> Vec<String> {
+            tags.iter()
+                .map(|tag| tags::normalize_tag_name(tag))
+                .filter(|tag| !tag.is_empty())
+                .collect()
+        };
+        let add = normalize(config.add);
+        let remove = normalize(config.remove);
+        if add.is_empty() && remove.is_empty() {
+            return Err(invalid("add か remove を指定してください".to_string()));
+        }
+
+        let hosts = config
+            .host
+            .iter()
+            .map(|host| host_regex(host).map_err(&invalid))
+            .collect::<Result<Vec<_>, _>>()?;
+        let paths = config
+            .path
+            .iter()
+            .map(|path| glob_regex(path).map_err(&invalid))
+            .collect::<Result<Vec<_>, _>>()?;
+        let compile = |pattern: Option<String>, field: &str| {
+            pattern
+                .map(|pattern| {
+                    Regex::new(&pattern).map_err(|e| invalid(format!("{field} が不正です: {e}")))
+                })
+                .transpose()
+        };
+        let url = compile(config.url_regex, "url_regex")?;
+        let title = compile(config.title_regex, "title_regex")?;
+        let has_tags = normalize(config.has_tags);
+
+        if hosts.is_empty()
+            && paths.is_empty()
+            && url.is_none()
+            && title.is_none()
+            && has_tags.is_empty()
+        {
+            return Err(invalid(
+                "条件（host, path, url_regex, title_regex, has_tags）がありません".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            name,
+            hosts,
+            paths,
+            url,
+            title,
+            has_tags,
+            add,
+            remove,
+        })
+    }
+
+    fn matches(&self, host: &str, path: &str, url: &str, title: &str, tags: &[String]) -> bool {
+        (self.hosts.is_empty() || self.hosts.iter().any(|re| re.is_match(host)))
+            && (self.paths.is_empty() || self.paths.iter().any(|re| re.is_match(path)))
+            && self.url.as_ref().is_none_or(|re| re.is_match(url))
+            && self.title.as_ref().is_none_or(|re| re.is_match(title))
+            && self.has_tags.iter().all(|tag| tags.contains(tag))
+    }
+}
+
+fn host_regex(pattern: &str) -> Result<Regex, String> {
+    let pattern = pattern.trim().to_lowercase();
+    if pattern.is_empty() {
+        return Err("host が空です".to_string());
+    }
+    if pattern.contains(['*', '?']) {
+        return glob_regex(&pattern);
+    }
+    Regex::new(&format!(r"^(.+\.)?{}$", regex::escape(&pattern))).map_err(|e| e.to_string())
+}
+
+// glob（`*`・`?`）を正規表現にする
+fn glob_regex(pattern: &str) -> Result<Regex, String> {
+    if pattern.is_empty() {
+        return Err("path が空です".to_string());
+    }
+    let body: String = pattern
+        .chars()
+        .map(|c| match c {
+            '*' => ".*".to_string(),
+            '?' => ".".to_string(),
+            c => regex::escape(&c.to_string()),
+        })
+        .collect();
+    Regex::new(&format!("^{body}$")).map_err(|e| e.to_string())
+}
+
+// 自動タグ付け
+pub fn auto_tagging(rules: &TagRules, url: &str, title: &str) -> String {
+    let mut tags = auto_tag_list(rules, url, title).0;
+    // 空の場合は空文字を返す
+    if tags.is_empty() {
+        return String::new();
+    }
+
+    // 重複削除とソート
+    tags.sort_unstable();
+    tags.dedup();
+
+    tags.join(", ")
+}
+
+// サイト名のタグにルールを適用したタグ一覧と、一致したルール名
+pub fn auto_tag_list(rules: &TagRules, url: &str, title: &str) -> (Vec<String>, Vec<String>) {
+    let mut tags: Vec<String> = Vec::with_capacity(3);
+
+    // URLクレートでサイト名を抽出
+    if let Ok(parsed_url) = Url::parse(url) {
+        if let Some(host) = parsed_url.host_str() {
+            // サイト名（登録可能ドメイン）の公開サフィックス直前のラベル
+            let label = public_suffix::site_identity(host).label;
+
+            if label.len() > 1 {
+                // サイト名のタグを追加
+                tags.push(label);
+            }
+        }
+    }
+
+    // ルールによるタグの追加・削除
+    let matched = rules.apply(url, title, &mut tags);
+    (tags, matched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preview(rules: &TagRules, url: &str, title: &str, tags: &[&str]) -> Vec<String> {
+        let mut tags: Vec<String> = tags.iter().map(ToString::to_string).collect();
+        rules.apply(url, title, &mut tags);
+        tags
+    }
+
+    #[test]
+    fn test_default_rules_are_valid() {
+        let rules = TagRules::parse(DEFAULT_RULES).unwrap();
+        assert!(!rules.is_empty());
+        assert_eq!(
+            preview(&rules, "https://gist.github.com/u/1", "", &["github"]),
+            ["github", "programming"]
+        );
+        assert_eq!(
+            preview(&rules, "https://youtu.be/abc", "", &["youtu"]),
+            ["youtu", "video"]
+        );
+    }
+
+    #[test]
+    fn test_rule_conditions() {
+        let rules = TagRules::parse(
+            r#"{"rules": [
+                {"name": "issues", "host": ["github.com"], "path": ["/*/issues/*"], "add": ["Issue"]},
+                {"name": "rust", "title_regex": "(?i)\\brust\\b", "add": ["rust"]},
+                {"name": "async rust", "has_tags": ["rust"], "url_regex": "tokio", "add": ["async"], "remove": ["github"]}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            preview(
+                &rules,
+                "https://github.com/tokio-rs/issues/1",
+                "Rust bug",
+                &["github"]
+            ),
+            ["issue", "rust", "async"]
+        );
+        assert_eq!(
+            preview(&rules, "https://github.com/tokio-rs", "Trusty", &["github"]),
+            ["github"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_rules_are_rejected() {
+        let error = |json: &str| TagRules::parse(json).unwrap_err();
+
+        assert!(
+            error(r#"{"rules": [{"name": "bad", "title_regex": "(", "add": ["x"]}]}"#)
+                .contains("「bad」")
+        );
+        assert!(error(r#"{"rules": [{"host": ["a.com"]}]}"#).contains("ルール1"));
+        assert!(error(r#"{"rules": [{"add": ["x"]}]}"#).contains("条件"));
+        assert!(error(r#"{"rules": [{"hosts": ["a.com"], "add": ["x"]}]}"#).contains("形式"));
+    }
+
+    #[test]
+    fn test_auto_tagging() {
+        let rules = TagRules::parse(DEFAULT_RULES).unwrap();
+        assert_eq!(
+            // prefix, suffix除去（単独）
+            auto_tagging(&rules, "https://www.sample.com", ""),
+            "sample"
+        );
+
+        assert_eq!(
+            // 一覧に無かったTLD・サブドメインごとに別サイトになるサフィックス
+            auto_tagging(&rules, "https://example.xyz", ""),
+            "example"
+        );
+        assert_eq!(
+            auto_tagging(&rules, "https://foo.github.io/blog", ""),
+            "foo"
+        );
+
+        assert_eq!(
+            // suffix除去（複合）
+            auto_tagging(&rules, "https://www.example.co.jp", ""),
+            "example"
+        );
+
+        assert_eq!(
+            // 推奨タグ
+            auto_tagging(&rules, "https://github.com/user", ""),
+            "github, programming"
+        );
+    }
+}
@@ -0,0 +1,504 @@
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use url::Url;
+
+use crate::{dates, public_suffix, sites};
+
+// 除いても同じページになるホストの先頭ラベル（残りが登録可能ドメインの場合のみ）
+const HOST_PREFIXES: &[&str] = &["www.", "m.", "mobile.", "amp."];
+
+// ディレクトリのインデックスとして扱うファイル名
+const INDEX_FILES: &[&str] = &["index.html", "index.htm"];
+
+/// 同じページと判定された記事のまとまり
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    pub canonical_url: String,
+    /// 統合時に残る記事が先頭（ゴミ箱外の記事を優先し、その中で最も古いもの）
+    pub articles: Vec<DuplicateArticle>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicateArticle {
+    pub id: i64,
+    pub url: String,
+    pub title: String,
+    pub created_at: DateTime<Utc>,
+    pub deleted: bool,
+}
+
+/// URLの正規形（重複判定のキー）
+///
+/// http/https の違い・`www.`や`m.`などのホスト・末尾のスラッシュ・`/index.html`・
+/// AMP（`?amp=1`・`amp.`ホスト・`.amp.html`・Google AMP キャッシュ）・youtu.be を同じキーにする。
+/// `/amp`のパスはAMP版と分かる場合だけ除く（`example.com/amp`は別のページ）。
+/// スキームは含めない（例: `example.com/post`）。http/https 以外はURLのまま
+pub fn canonical_url(url: &str) -> String {
+    canonical_key(url, false)
+}
+
+// `from_amp_cache`: AMP キャッシュのURLから取り出した元のページ（AMP版と分かっている）
+fn canonical_key(url: &str, from_amp_cache: bool) -> String {
+    let Ok(parsed) = Url::parse(url) else {
+        return url.to_string();
+    };
+    let Some(host) = parsed.host_str() else {
+        return url.to_string();
+    };
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return url.to_string();
+    }
+
+    let mut host = sites::normalize_host(host);
+    let mut segments: Vec<&str> = parsed.path().split('/').filter(|s| !s.is_empty()).collect();
+    let mut params: Vec<&str> = parsed
+        .query()
+        .unwrap_or("")
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .collect();
+
+    // Google AMP キャッシュは元のページのURLを含む
+    if let Some(original) = amp_cache_target(&host, &segments) {
+        let query = parsed.query().map(|q| format!("?{q}")).unwrap_or_default();
+        return canonical_key(&format!("https://{original}{query}"), true);
+    }
+    let amp_host = host.starts_with("amp.");
+
+    while let Some(rest) = HOST_PREFIXES
+        .iter()
+        .find_map(|prefix| host.strip_prefix(prefix))
+        .filter(|rest| public_suffix::registrable_domain(rest).is_some())
+    {
+        host = rest.to_string();
+    }
+
+    // youtu.be/ID・/shorts/ID・/embed/ID → youtube.com/watch?v=ID（再生位置などは除く）
+    let video_param;
+    let video_id = match (host.as_str(), segments.as_slice()) {
+        ("youtu.be", [id, ..]) | ("youtube.com", ["shorts" | "embed" | "live", id, ..]) => {
+            Some(*id)
+        }
+        ("youtube.com", ["watch"]) => params
+            .iter()
+            .find_map(|pair| pair.strip_prefix("v="))
+            .filter(|id| !id.is_empty()),
+        _ => None,
+    };
+    if let Some(id) = video_id {
+        host = "youtube.com".to_string();
+        segments = vec!["watch"];
+        video_param = format!("v={id}");
+        params = vec![&video_param];
+    }
+
+    // AMP版のページ
+    let param_count = params.len();
+    params.retain(|pair| {
+        !matches!(
+            pair.to_ascii_lowercase().as_str(),
+            "amp" | "amp=" | "amp=1" | "amp=true" | "outputtype=amp"
+        )
+    });
+    let is_amp = from_amp_cache || amp_host || params.len() < param_count;
+    if is_amp && segments.last() == Some(&"amp") {
+        segments.pop();
+    } else if is_amp && segments.first() == Some(&"amp") {
+        segments.remove(0);
+    }
+    let amp_file;
+    if let Some(stem) = segments.last().and_then(|s| s.strip_suffix(".amp.html")) {
+        amp_file = format!("{stem}.html");
+        segments.pop();
+        segments.push(&amp_file);
+    }
+
+    if segments
+        .last()
+        .is_some_and(|last| INDEX_FILES.contains(&last.to_ascii_lowercase().as_str()))
+    {
+        segments.pop();
+    }
+
+    let mut key = host;
+    if let Some(port) = parsed.port() {
+        key.push(':');
+        key.push_str(&port.to_string());
+    }
+    for segment in segments {
+        key.push('/');
+        key.push_str(segment);
+    }
+    // パラメータの順序が違うだけのURLは同じページ
+    params.sort_unstable();
+    if !params.is_empty() {
+        key.push('?');
+        key.push_str(&params.join("&"));
+    }
+    key
+}
+
+// www.google.com/amp/s/example.com/post・example-com.cdn.ampproject.org/c/s/example.com/post
+fn amp_cache_target(host: &str, segments: &[&str]) -> Option<String> {
+    let rest = match segments {
+        ["amp", "s", rest @ ..] | ["amp", rest @ ..]
+            if host == "google.com" || host == "www.google.com" =>
+        {
+            rest
+        }
+        ["c" | "v", "s", rest @ ..] | ["c" | "v", rest @ ..]
+            if host.ends_with(".cdn.ampproject.org") =>
+        {
+            rest
+        }
+        _ => return None,
+    };
+    (!rest.is_empty()).then(|| rest.join("/"))
+}
+
+/// 正規形のキーが未設定の記事に設定する（同じキーの記事が既にあれば NULL のまま）
+/// 正規形の導入前に保存された記事のための処理。設定した件数を返す
+pub fn backfill_canonical_urls(db: &Connection) -> Result<usize, String> {
+    let mut stmt = db
+        .prepare("SELECT id, url FROM articles WHERE canonical_url IS NULL ORDER BY id")
+        .map_err(|e| e.to_string())?;
+    let articles = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    drop(stmt);
+
+    let mut filled = 0;
+    for (id, url) in articles {
+        filled += db
+            .execute(
+                "UPDATE OR IGNORE articles SET canonical_url = ? WHERE id = ?",
+                params![canonical_url(&url), id],
+            )
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(filled)
+}
+
+/// 正規形が同じ記事の一覧（ゴミ箱内の記事も含む）
+pub fn find_duplicates(db: &Connection) -> Result<Vec<DuplicateGroup>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT id, url, title, created_at, deleted_at IS NOT NULL
+            FROM articles
+            ORDER BY deleted_at IS NOT NULL, created_at, id",
+        )
+        .map_err(|e| e.to_string())?;
+    let articles = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, bool>(4)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut groups: BTreeMap<String, Vec<DuplicateArticle>> = BTreeMap::new();
+    for (id, url, title, created_at, deleted) in articles {
+        groups
+            .entry(canonical_url(&url))
+            .or_default()
+            .push(DuplicateArticle {
+                id,
+                url,
+                title,
+                created_at: dates::parse_db_timestamp(&created_at)?,
+                deleted,
+            });
+    }
+
+    Ok(groups
+        .into_iter()
+        .filter(|(_, articles)| articles.len() > 1)
+        .map(|(canonical_url, articles)| DuplicateGroup {
+            canonical_url,
+            articles,
+        })
+        .collect())
+}
+
+/// 重複した記事を1件に統合する。`canonical_url`を指定すればそのまとまりのみ
+///
+/// 先頭の記事を残し、タグ・ステータス履歴・メモを引き継ぐ。ステータスは最後に変更された記事のもの、
+/// 登録日時は最も古いもの、更新日時は最も新しいものにする。削除した記事の数を返す
+pub fn merge_duplicates(db: &Connection, canonical_url: Option<&str>) -> Result<usize, String> {
+    let groups = find_duplicates(db)?;
+
+    let mut merged = 0;
+    for group in groups
+        .iter()
+        .filter(|group| canonical_url.is_none_or(|key| key == group.canonical_url))
+    {
+        let (target, sources) = group.articles.split_first().ok_or("記事がありません")?;
+        for source in sources {
+            merge_articles(db, source.id, target.id)?;
+            merged += 1;
+        }
+        db.execute(
+            "UPDATE articles SET canonical_url = ? WHERE id = ?",
+            params![group.canonical_url, target.id],
+        )
+        .map_err(|e| e.to_string())?;
+
+        log!(
+            "記事統合: {}件 → {} (ID: {})",
+            sources.len(),
+            target.url,
+            target.id
+        );
+    }
+
+    Ok(merged)
+}
+
+struct MergeState {
+    notes: String,
+    status_id: Option<i64>,
+    status_changed_at: Option<String>,
+    created_at: String,
+    updated_at: String,
+    deleted_at: Option<String>,
+}
+
+fn merge_state(db: &Connection, article_id: i64) -> Result<MergeState, String> {
+    db.query_row(
+        "SELECT notes, status_id, status_changed_at, created_at, updated_at, deleted_at
+        FROM articles
+        WHERE id = ?",
+        [article_id],
+        |row| {
+            Ok(MergeState {
+                notes: row.get(0)?,
+                status_id: row.get(1)?,
+                status_changed_at: row.get(2)?,
+                created_at: row.get(3)?,
+                updated_at: row.get(4)?,
+                deleted_at: row.get(5)?,
+            })
+        },
+    )
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("記事が見つかりません (ID: {article_id})"))
+}
+
+/// 記事`source`を`target`へ統合し、`source`を削除する（タグ・メモ・ステータスの扱いは`merge_duplicates`と同じ）
+pub fn merge_articles(db: &Connection, source: i64, target: i64) -> Result<(), String> {
+    let from = merge_state(db, source)?;
+    let into = merge_state(db, target)?;
+
+    db.execute(
+        "INSERT OR IGNORE INTO article_tags (article_id, tag_id)
+         SELECT ?, tag_id FROM article_tags WHERE article_id = ?",
+        params![target, source],
+    )
+    .map_err(|e| e.to_string())?;
+    db.execute(
+        "UPDATE article_status_history SET article_id = ? WHERE article_id = ?",
+        params![target, source],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let notes = merge_notes(&into.notes, &from.notes);
+    // ゴミ箱外の記事が1つでもあれば統合後もゴミ箱外
+    let deleted_at = into.deleted_at.zip(from.deleted_at).map(|(a, b)| a.max(b));
+
+    db.execute(
+        "UPDATE articles
+         SET notes = ?, created_at = ?, updated_at = ?, deleted_at = ?
+         WHERE id = ?",
+        params![
+            notes,
+            into.created_at.min(from.created_at),
+            into.updated_at.max(from.updated_at),
+            deleted_at,
+            target
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    if from.status_changed_at > into.status_changed_at && from.status_id != into.status_id {
+        // ステータス変更のトリガーが記録する履歴は、引き継いだ履歴と重複するので消す
+        let last_history: i64 = db
+            .query_row(
+                "SELECT COALESCE(MAX(id), 0) FROM article_status_history",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+        db.execute(
+            "UPDATE articles SET status_id = ? WHERE id = ?",
+            params![from.status_id, target],
+        )
+        .map_err(|e| e.to_string())?;
+        db.execute(
+            "DELETE FROM article_status_history WHERE id > ?",
+            [last_history],
+        )
+        .map_err(|e| e.to_string())?;
+        db.execute(
+            "UPDATE articles SET status_changed_at = ? WHERE id = ?",
+            params![from.status_changed_at, target],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    db.execute("DELETE FROM articles WHERE id = ?", [source])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// 統合後のメモ（空でない方。両方あれば統合先 → 統合元の順に連結）
+pub fn merge_notes(into: &str, from: &str) -> String {
+    match (into.trim(), from.trim()) {
+        (_, "") => into.to_string(),
+        ("", _) => from.to_string(),
+        (a, b) if a == b => into.to_string(),
+        _ => format!("{}\n\n{}", into.trim_end(), from.trim_start()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init_database;
+
+    #[test]
+    fn test_canonical_url() {
+        let cases = [
+            ("https://example.com/post", "example.com/post"),
+            ("http://www.example.com/post/", "example.com/post"),
+            ("https://m.example.com/post/index.html", "example.com/post"),
+            ("https://example.com/", "example.com"),
+            ("https://example.com:8080/a?b=1", "example.com:8080/a?b=1"),
+            ("https://example.com/a?b=2&a=1", "example.com/a?a=1&b=2"),
+            // AMP
+            ("https://amp.example.com/post/amp", "example.com/post"),
+            ("https://example.com/amp/post?amp=1", "example.com/post"),
+            ("https://amp.example.com/post?amp=1", "example.com/post"),
+            ("https://example.com/post.amp.html", "example.com/post.html"),
+            (
+                "https://www.google.com/amp/s/example.com/post/amp",
+                "example.com/post",
+            ),
+            (
+                "https://example-com.cdn.ampproject.org/c/s/www.example.com/post",
+                "example.com/post",
+            ),
+            // AMP版と分からない /amp は別のページ
+            ("https://example.com/amp", "example.com/amp"),
+            ("https://example.com/docs/amp", "example.com/docs/amp"),
+            ("https://example.com/amp/post", "example.com/amp/post"),
+            // YouTube
+            ("https://youtu.be/abc123?t=10", "youtube.com/watch?v=abc123"),
+            (
+                "https://m.youtube.com/watch?t=10&v=abc123",
+                "youtube.com/watch?v=abc123",
+            ),
+            (
+                "https://www.youtube.com/shorts/abc123",
+                "youtube.com/watch?v=abc123",
+            ),
+            // 残りが公開サフィックスになるラベルは除かない
+            ("https://m.co.uk/post", "m.co.uk/post"),
+            // http/https 以外はそのまま
+            (
+                "file:///C:/Users/test/document.html",
+                "file:///C:/Users/test/document.html",
+            ),
+            ("not a url", "not a url"),
+        ];
+        for (url, expected) in cases {
+            assert_eq!(canonical_url(url), expected, "{url}");
+        }
+    }
+
+    #[test]
+    fn test_find_and_merge_duplicates() {
+        let db = init_database(":memory:").unwrap();
+        db.execute_batch(
+            "INSERT INTO tags (name) VALUES ('rust'), ('async');
+             INSERT INTO articles (url, title, notes, created_at, updated_at)
+               VALUES ('http://example.com/post/', 'old', 'first', '2025-01-01 00:00:00', '2025-01-01 00:00:00');
+             INSERT INTO articles (url, title, notes, created_at, updated_at)
+               VALUES ('https://www.example.com/post', 'new', 'second', '2025-02-01 00:00:00', '2025-02-01 00:00:00');
+             INSERT INTO articles (url, title) VALUES ('https://example.com/other', 'other');
+             INSERT INTO article_tags (article_id, tag_id) VALUES (1, 1), (2, 1), (2, 2);
+             UPDATE articles SET status_id = (SELECT id FROM statuses WHERE name = 'done') WHERE id = 2;
+             UPDATE articles SET status_changed_at = created_at;",
+        )
+        .unwrap();
+        // 導入前の記事の扱い（重複した記事はキーが空のまま）
+        db.execute("UPDATE articles SET canonical_url = NULL", [])
+            .unwrap();
+        assert_eq!(backfill_canonical_urls(&db).unwrap(), 2);
+
+        let groups = find_duplicates(&db).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].canonical_url, "example.com/post");
+        let ids: Vec<i64> = groups[0].articles.iter().map(|a| a.id).collect();
+        assert_eq!(ids, [1, 2]);
+
+        assert_eq!(merge_duplicates(&db, None).unwrap(), 1);
+        assert!(find_duplicates(&db).unwrap().is_empty());
+
+        let (title, notes, status, created_at, updated_at, key): (
+            String,
+            String,
+            String,
+            String,
+            String,
+            String,
+        ) = db
+            .query_row(
+                "SELECT a.title, a.notes, st.name, a.created_at, a.updated_at, a.canonical_url
+                FROM articles a
+                JOIN statuses st
+                  ON a.status_id = st.id
+                WHERE a.id = 1",
+                [],
+                |row| {
+                    Ok((
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                        row.get(5)?,
+                    ))
+                },
+            )
+            .unwrap();
+        assert_eq!(title, "old");
+        assert_eq!(notes, "first\n\nsecond");
+        assert_eq!(status, "done");
+        assert_eq!(created_at, "2025-01-01 00:00:00");
+        assert_eq!(updated_at, "2025-02-01 00:00:00");
+        assert_eq!(key, "example.com/post");
+
+        let tag_count: u32 = db
+            .query_row(
+                "SELECT COUNT(*) FROM article_tags WHERE article_id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(tag_count, 2);
+    }
+}
@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::resave::ResavePolicy;
+use crate::url_rules::UrlRules;
+
+/// 設定ファイル（`config.json`）の既定の場所
+pub const CONFIG_FILE: &str = "config.json";
+
+// ゴミ箱の記事を自動で完全削除するまでの日数（既定値）
+pub const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+
+/// 設定ファイル（アプリ本体・CLIで共通）
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Config {
+    pub database_path: String,
+    // ゴミ箱の保存期間（日）。0なら自動削除しない
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    // 自動タグ付けのルールファイル（無ければ同梱の既定ルール）
+    #[serde(default = "default_tag_rules_path")]
+    pub tag_rules_path: String,
+    // URL正規化のルール（省略時は既定のルール）
+    #[serde(default)]
+    pub url_normalization: UrlRules,
+    // 再保存時のタグ・タイトルの扱い（省略時はタグを追加・タイトルを保持）
+    #[serde(default)]
+    pub resave: ResavePolicy,
+}
+
+const fn default_trash_retention_days() -> u32 {
+    DEFAULT_TRASH_RETENTION_DAYS
+}
+
+fn default_tag_rules_path() -> String {
+    "tag_rules.json".to_string()
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            database_path: "atode.db".to_string(),
+            trash_retention_days: DEFAULT_TRASH_RETENTION_DAYS,
+            tag_rules_path: default_tag_rules_path(),
+            url_normalization: UrlRules::default(),
+            resave: ResavePolicy::default(),
+        }
+    }
+}
+
+impl Config {
+    /// 設定ファイルを読む（読めない・解釈できない場合はエラー）
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        serde_json::from_str(&content).map_err(|e| e.to_string())
+    }
+}
+
+/// URLを既定のブラウザで開く
+pub fn open_url(url: &str) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        Command::new("cmd")
+            .args(["/c", "start", url])
+            .spawn()
+            .map_err(|e| e.to_string())?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        Command::new("open")
+            .arg(url)
+            .spawn()
+            .map_err(|e| e.to_string())?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        Command::new("xdg-open")
+            .arg(url)
+            .spawn()
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_defaults_for_missing_fields() {
+        let config: Config = serde_json::from_str(r#"{"database_path": "my.db"}"#).unwrap();
+        assert_eq!(config.database_path, "my.db");
+        assert_eq!(config.trash_retention_days, DEFAULT_TRASH_RETENTION_DAYS);
+        assert_eq!(config.tag_rules_path, "tag_rules.json");
+
+        let missing = Config::read(Path::new("does-not-exist/config.json"));
+        assert!(missing.is_err());
+    }
+}
@@ -0,0 +1,200 @@
+use chrono::{DateTime, Datelike, Days, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+// SQLiteのCURRENT_TIMESTAMP形式（UTC・タイムゾーン表記なし）
+const DB_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// DBの日時文字列（UTC）を読む
+pub fn parse_db_timestamp(value: &str) -> Result<DateTime<Utc>, String> {
+    NaiveDateTime::parse_from_str(value, DB_FORMAT)
+        .map(|naive| naive.and_utc())
+        .map_err(|e| format!("日時「{value}」を読めません: {e}"))
+}
+
+/// DBの日時文字列（UTC）にする。検索条件は文字列のまま比較する
+pub fn to_db_timestamp(instant: DateTime<Utc>) -> String {
+    instant.format(DB_FORMAT).to_string()
+}
+
+/// 日時の指定が表す時刻・期間
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Span {
+    /// 時刻（RFC 3339・`now`）
+    Instant(DateTime<Utc>),
+    /// 日単位の期間（開始以上・終了未満）
+    Period(DateTime<Utc>, DateTime<Utc>),
+}
+
+impl Span {
+    /// 時刻、または期間の始まり
+    pub const fn start(self) -> DateTime<Utc> {
+        match self {
+            Self::Instant(instant) | Self::Period(instant, _) => instant,
+        }
+    }
+}
+
+/// 日時の指定を現在のローカルタイムゾーンで解釈する（`resolve_at`参照）
+pub fn resolve(expr: &str) -> Result<DateTime<Utc>, String> {
+    resolve_at(expr, &Local::now())
+}
+
+/// 日時の指定を`now`のタイムゾーンで解釈し、その時刻（期間なら始まり。UTC）を返す
+///
+/// - RFC 3339（`2026-01-01T09:00:00+09:00`）: その時刻
+/// - 日付（`2026-01-01`）: その日の0時
+/// - `today` / `yesterday` / `this week`（月曜始まり） / `this month` / `this year`: その期間の始まり
+/// - `last N days`（`7d`）: 今日を含むN日間の始まり（今日の0時のN-1日前）
+/// - `N days ago`: N日前の0時（`weeks`・`months`も可）
+/// - `now`: 現在時刻
+pub fn resolve_at<Tz: TimeZone>(expr: &str, now: &DateTime<Tz>) -> Result<DateTime<Utc>, String> {
+    resolve_span_at(expr, now).map(Span::start)
+}
+
+/// 日時の指定を現在のローカルタイムゾーンで時刻・期間として解釈する（`resolve_span_at`参照）
+pub fn resolve_span(expr: &str) -> Result<Span, String> {
+    resolve_span_at(expr, &Local::now())
+}
+
+/// 日時の指定を`now`のタイムゾーンで時刻・期間として解釈する
+///
+/// 日付・`today`・`yesterday`・`N days ago`はその1日、
+/// `this week`・`last N days`などは始まりから今日の終わりまで。RFC 3339・`now`は時刻
+pub fn resolve_span_at<Tz: TimeZone>(expr: &str, now: &DateTime<Tz>) -> Result<Span, String> {
+    let expr = expr.trim();
+    if let Ok(instant) = DateTime::parse_from_rfc3339(expr) {
+        return Ok(Span::Instant(instant.with_timezone(&Utc)));
+    }
+
+    let tz = now.timezone();
+    let today = now.date_naive();
+    let lower = expr.to_lowercase();
+    let words: Vec<&str> = lower.split_whitespace().collect();
+
+    // 期間の最初の日と、終わりの日（その日を含む）
+    let days = match words.as_slice() {
+        ["now"] => return Ok(Span::Instant(now.with_timezone(&Utc))),
+        ["today"] => Some((today, today)),
+        ["yesterday"] => today.checked_sub_days(Days::new(1)).map(|day| (day, day)),
+        ["this", "week"] => today
+            .checked_sub_days(Days::new(u64::from(today.weekday().num_days_from_monday())))
+            .map(|day| (day, today)),
+        ["this", "month"] => today.with_day(1).map(|day| (day, today)),
+        ["this", "year"] => today.with_ordinal(1).map(|day| (day, today)),
+        ["last", count, unit] => days_back(today, count, unit)
+            .and_then(|day| day.checked_add_days(Days::new(1)))
+            .map(|day| (day, today)),
+        [count, unit, "ago"] => days_back(today, count, unit).map(|day| (day, day)),
+        [short] => short
+            .strip_suffix('d')
+            .and_then(|count| days_back(today, count, "days"))
+            .and_then(|day| day.checked_add_days(Days::new(1)))
+            .map(|day| (day, today)),
+        _ => None,
+    };
+    let (first, last) = match days {
+        Some(days) => days,
+        None => NaiveDate::parse_from_str(expr, "%Y-%m-%d")
+            .map(|day| (day, day))
+            .map_err(|_| {
+                format!(
+                    "日時「{expr}」を解釈できません（例: 2026-01-01, 2026-01-01T09:00:00+09:00, last 7 days, 3 days ago）"
+                )
+            })?,
+    };
+    let end = last
+        .checked_add_days(Days::new(1))
+        .ok_or("日付が範囲外です")?;
+
+    Ok(Span::Period(
+        start_of_day(&tz, first)?,
+        start_of_day(&tz, end)?,
+    ))
+}
+
+/// ローカルタイムゾーンでの日付の始まり
+pub fn local_start_of_day(day: NaiveDate) -> Result<DateTime<Utc>, String> {
+    start_of_day(&Local, day)
+}
+
+fn start_of_day<Tz: TimeZone>(tz: &Tz, day: NaiveDate) -> Result<DateTime<Utc>, String> {
+    let midnight = day.and_hms_opt(0, 0, 0).ok_or("日付が範囲外です")?;
+    // 夏時間の切り替えで0時が存在しない日は、存在する最初の時刻にする
+    (0..=2)
+        .find_map(|hour| {
+            tz.from_local_datetime(&(midnight + Duration::hours(hour)))
+                .earliest()
+        })
+        .map(|instant| instant.with_timezone(&Utc))
+        .ok_or_else(|| format!("{day} の0時を解釈できません"))
+}
+
+// 今日から`count`単位だけ遡った日
+fn days_back(today: NaiveDate, count: &str, unit: &str) -> Option<NaiveDate> {
+    let count: u32 = count.parse().ok()?;
+    match unit {
+        "day" | "days" => today.checked_sub_days(Days::new(u64::from(count))),
+        "week" | "weeks" => today.checked_sub_days(Days::new(u64::from(count) * 7)),
+        "month" | "months" => today.checked_sub_months(chrono::Months::new(count)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    // 2026-03-05（木）01:30 JST = 2026-03-04 16:30 UTC
+    fn jst_now() -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339("2026-03-05T01:30:00+09:00").unwrap()
+    }
+
+    fn resolved(expr: &str) -> String {
+        to_db_timestamp(resolve_at(expr, &jst_now()).unwrap())
+    }
+
+    #[test]
+    fn test_relative_expressions_use_local_days() {
+        // UTCではまだ3/4だが、JSTの「今日」は3/5
+        assert_eq!(resolved("today"), "2026-03-04 15:00:00");
+        assert_eq!(resolved("yesterday"), "2026-03-03 15:00:00");
+        assert_eq!(resolved("last 7 days"), "2026-02-26 15:00:00");
+        assert_eq!(resolved("7d"), "2026-02-26 15:00:00");
+        assert_eq!(resolved("3 days ago"), "2026-03-01 15:00:00");
+        assert_eq!(resolved("1 month ago"), "2026-02-04 15:00:00");
+        assert_eq!(resolved("This Week"), "2026-03-01 15:00:00");
+        assert_eq!(resolved("this month"), "2026-02-28 15:00:00");
+        assert_eq!(resolved("now"), "2026-03-04 16:30:00");
+    }
+
+    #[test]
+    fn test_absolute_expressions() {
+        assert_eq!(resolved("2026-01-01"), "2025-12-31 15:00:00");
+        assert_eq!(resolved("2026-01-01T12:00:00Z"), "2026-01-01 12:00:00");
+        assert!(resolve_at("someday", &jst_now()).is_err());
+        assert!(resolve_at("2026-02-30", &jst_now()).is_err());
+
+        let stored = parse_db_timestamp("2025-06-01 09:00:00").unwrap();
+        assert_eq!(stored.to_rfc3339(), "2025-06-01T09:00:00+00:00");
+    }
+
+    #[test]
+    fn test_day_expressions_resolve_to_periods() {
+        let period = |expr: &str| match resolve_span_at(expr, &jst_now()).unwrap() {
+            Span::Period(start, end) => (to_db_timestamp(start), to_db_timestamp(end)),
+            Span::Instant(instant) => panic!("{expr} は期間のはず: {instant}"),
+        };
+        let yesterday = (
+            "2026-03-03 15:00:00".to_string(),
+            "2026-03-04 15:00:00".to_string(),
+        );
+        assert_eq!(period("yesterday"), yesterday);
+        assert_eq!(period("2026-03-04"), yesterday);
+        assert_eq!(period("1 day ago"), yesterday);
+        assert_eq!(period("last 7 days").1, "2026-03-05 15:00:00");
+        assert!(matches!(
+            resolve_span_at("now", &jst_now()).unwrap(),
+            Span::Instant(_)
+        ));
+    }
+}
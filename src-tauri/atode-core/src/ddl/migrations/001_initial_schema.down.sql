@@ -0,0 +1,5 @@
+-- v1 初期スキーマの巻き戻し（依存関係の逆順で削除）
+DROP TABLE IF EXISTS article_tags;
+DROP TABLE IF EXISTS tags;
+DROP TABLE IF EXISTS articles;
+DROP TABLE IF EXISTS sites;
@@ -0,0 +1,25 @@
+-- 記事の全文検索インデックス（タイトル・URL）
+-- articles を外部コンテンツとし、トリガーで同期する
+CREATE VIRTUAL TABLE IF NOT EXISTS articles_fts USING fts5(
+    title,
+    url,
+    content = 'articles',
+    content_rowid = 'id',
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER IF NOT EXISTS articles_fts_ai AFTER INSERT ON articles BEGIN
+    INSERT INTO articles_fts(rowid, title, url) VALUES (new.id, new.title, new.url);
+END;
+
+CREATE TRIGGER IF NOT EXISTS articles_fts_ad AFTER DELETE ON articles BEGIN
+    INSERT INTO articles_fts(articles_fts, rowid, title, url) VALUES ('delete', old.id, old.title, old.url);
+END;
+
+CREATE TRIGGER IF NOT EXISTS articles_fts_au AFTER UPDATE OF title, url ON articles BEGIN
+    INSERT INTO articles_fts(articles_fts, rowid, title, url) VALUES ('delete', old.id, old.title, old.url);
+    INSERT INTO articles_fts(rowid, title, url) VALUES (new.id, new.title, new.url);
+END;
+
+-- 既存の記事をインデックスへ取り込む
+INSERT INTO articles_fts(articles_fts) VALUES ('rebuild');
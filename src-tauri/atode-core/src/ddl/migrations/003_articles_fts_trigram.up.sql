@@ -0,0 +1,29 @@
+-- 全文検索インデックスを trigram トークナイザで作り直す
+-- 日本語のように空白で区切られないタイトルでも、文字の並びで部分一致できるようにする
+DROP TRIGGER IF EXISTS articles_fts_au;
+DROP TRIGGER IF EXISTS articles_fts_ad;
+DROP TRIGGER IF EXISTS articles_fts_ai;
+DROP TABLE IF EXISTS articles_fts;
+
+CREATE VIRTUAL TABLE articles_fts USING fts5(
+    title,
+    url,
+    content = 'articles',
+    content_rowid = 'id',
+    tokenize = 'trigram'
+);
+
+CREATE TRIGGER articles_fts_ai AFTER INSERT ON articles BEGIN
+    INSERT INTO articles_fts(rowid, title, url) VALUES (new.id, new.title, new.url);
+END;
+
+CREATE TRIGGER articles_fts_ad AFTER DELETE ON articles BEGIN
+    INSERT INTO articles_fts(articles_fts, rowid, title, url) VALUES ('delete', old.id, old.title, old.url);
+END;
+
+CREATE TRIGGER articles_fts_au AFTER UPDATE OF title, url ON articles BEGIN
+    INSERT INTO articles_fts(articles_fts, rowid, title, url) VALUES ('delete', old.id, old.title, old.url);
+    INSERT INTO articles_fts(rowid, title, url) VALUES (new.id, new.title, new.url);
+END;
+
+INSERT INTO articles_fts(articles_fts) VALUES ('rebuild');
@@ -0,0 +1,7 @@
+DROP TRIGGER IF EXISTS articles_status_au;
+DROP TRIGGER IF EXISTS articles_status_ai;
+DROP TABLE IF EXISTS article_status_history;
+DROP INDEX IF EXISTS idx_articles_status_id;
+ALTER TABLE articles DROP COLUMN status_changed_at;
+ALTER TABLE articles DROP COLUMN status_id;
+DROP TABLE IF EXISTS statuses;
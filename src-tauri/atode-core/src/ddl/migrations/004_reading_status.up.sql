@@ -0,0 +1,58 @@
+-- 読書ステータス（inbox / reading / done / reference、ユーザーが追加・変更できる）
+-- position が最も小さいステータスが新規記事の初期ステータスになる
+CREATE TABLE statuses (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT UNIQUE NOT NULL,
+    position INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO statuses (name, position) VALUES
+    ('inbox', 0),
+    ('reading', 1),
+    ('done', 2),
+    ('reference', 3);
+
+-- ALTER TABLE で追加する列には外部キーを付けない（付けると DROP COLUMN で巻き戻せないため）
+-- ステータス削除時の付け替えはアプリ側で行う
+ALTER TABLE articles ADD COLUMN status_id INTEGER;
+ALTER TABLE articles ADD COLUMN status_changed_at DATETIME;
+
+CREATE INDEX idx_articles_status_id ON articles(status_id);
+
+-- ステータス変更履歴
+CREATE TABLE article_status_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    article_id INTEGER NOT NULL,
+    status_id INTEGER,
+    changed_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (article_id) REFERENCES articles(id) ON DELETE CASCADE,
+    FOREIGN KEY (status_id) REFERENCES statuses(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_article_status_history_article_id ON article_status_history(article_id);
+
+-- 既存記事はすべて inbox から開始
+UPDATE articles
+SET status_id = (SELECT id FROM statuses WHERE name = 'inbox'),
+    status_changed_at = created_at;
+
+INSERT INTO article_status_history (article_id, status_id, changed_at)
+SELECT id, status_id, created_at FROM articles;
+
+-- 新規記事には初期ステータスを付与（どの経路で INSERT されても同じ扱いにする）
+CREATE TRIGGER articles_status_ai AFTER INSERT ON articles
+WHEN new.status_id IS NULL
+BEGIN
+    UPDATE articles
+    SET status_id = (SELECT id FROM statuses ORDER BY position, id LIMIT 1)
+    WHERE id = new.id;
+END;
+
+-- ステータスが変わったら変更日時と履歴を記録
+CREATE TRIGGER articles_status_au AFTER UPDATE OF status_id ON articles
+WHEN new.status_id IS NOT old.status_id
+BEGIN
+    UPDATE articles SET status_changed_at = CURRENT_TIMESTAMP WHERE id = new.id;
+    INSERT INTO article_status_history (article_id, status_id) VALUES (new.id, new.status_id);
+END;
@@ -0,0 +1,30 @@
+-- メモを全文検索の対象から外してから列を削除
+DROP TRIGGER IF EXISTS articles_fts_au;
+DROP TRIGGER IF EXISTS articles_fts_ad;
+DROP TRIGGER IF EXISTS articles_fts_ai;
+DROP TABLE IF EXISTS articles_fts;
+
+CREATE VIRTUAL TABLE articles_fts USING fts5(
+    title,
+    url,
+    content = 'articles',
+    content_rowid = 'id',
+    tokenize = 'trigram'
+);
+
+CREATE TRIGGER articles_fts_ai AFTER INSERT ON articles BEGIN
+    INSERT INTO articles_fts(rowid, title, url) VALUES (new.id, new.title, new.url);
+END;
+
+CREATE TRIGGER articles_fts_ad AFTER DELETE ON articles BEGIN
+    INSERT INTO articles_fts(articles_fts, rowid, title, url) VALUES ('delete', old.id, old.title, old.url);
+END;
+
+CREATE TRIGGER articles_fts_au AFTER UPDATE OF title, url ON articles BEGIN
+    INSERT INTO articles_fts(articles_fts, rowid, title, url) VALUES ('delete', old.id, old.title, old.url);
+    INSERT INTO articles_fts(rowid, title, url) VALUES (new.id, new.title, new.url);
+END;
+
+INSERT INTO articles_fts(articles_fts) VALUES ('rebuild');
+
+ALTER TABLE articles DROP COLUMN notes;
@@ -0,0 +1,32 @@
+-- 記事ごとのメモ（Markdown）
+ALTER TABLE articles ADD COLUMN notes TEXT NOT NULL DEFAULT '';
+
+-- メモも全文検索の対象にする
+DROP TRIGGER IF EXISTS articles_fts_au;
+DROP TRIGGER IF EXISTS articles_fts_ad;
+DROP TRIGGER IF EXISTS articles_fts_ai;
+DROP TABLE IF EXISTS articles_fts;
+
+CREATE VIRTUAL TABLE articles_fts USING fts5(
+    title,
+    url,
+    notes,
+    content = 'articles',
+    content_rowid = 'id',
+    tokenize = 'trigram'
+);
+
+CREATE TRIGGER articles_fts_ai AFTER INSERT ON articles BEGIN
+    INSERT INTO articles_fts(rowid, title, url, notes) VALUES (new.id, new.title, new.url, new.notes);
+END;
+
+CREATE TRIGGER articles_fts_ad AFTER DELETE ON articles BEGIN
+    INSERT INTO articles_fts(articles_fts, rowid, title, url, notes) VALUES ('delete', old.id, old.title, old.url, old.notes);
+END;
+
+CREATE TRIGGER articles_fts_au AFTER UPDATE OF title, url, notes ON articles BEGIN
+    INSERT INTO articles_fts(articles_fts, rowid, title, url, notes) VALUES ('delete', old.id, old.title, old.url, old.notes);
+    INSERT INTO articles_fts(rowid, title, url, notes) VALUES (new.id, new.title, new.url, new.notes);
+END;
+
+INSERT INTO articles_fts(articles_fts) VALUES ('rebuild');
@@ -0,0 +1,5 @@
+-- ゴミ箱内の記事は巻き戻し後に通常の記事として見えてしまうため、先に完全削除する
+DELETE FROM articles WHERE deleted_at IS NOT NULL;
+
+DROP INDEX IF EXISTS idx_articles_deleted_at;
+ALTER TABLE articles DROP COLUMN deleted_at;
@@ -0,0 +1,4 @@
+-- ゴミ箱（論理削除）。NULLなら通常の記事、値があればゴミ箱へ移した日時
+ALTER TABLE articles ADD COLUMN deleted_at DATETIME;
+
+CREATE INDEX IF NOT EXISTS idx_articles_deleted_at ON articles(deleted_at);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_tag_aliases_tag_id;
+DROP TABLE IF EXISTS tag_aliases;
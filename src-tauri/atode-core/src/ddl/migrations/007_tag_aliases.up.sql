@@ -0,0 +1,10 @@
+-- タグの別名（例: js → javascript）。別名で保存・検索すると正式なタグとして扱う
+-- 別名はタグ名と同じ正規化（NFKC・前後空白除去・小文字化）を済ませた値で保存する
+CREATE TABLE IF NOT EXISTS tag_aliases (
+    alias TEXT PRIMARY KEY,
+    tag_id INTEGER NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_tag_aliases_tag_id ON tag_aliases(tag_id);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_site_aliases_site_id;
+DROP TABLE IF EXISTS site_aliases;
@@ -0,0 +1,10 @@
+-- ホスト名の別名（例: m.youtube.com, youtu.be → youtube.com）
+-- 別名のホストから保存した記事は参照先のサイトに紐づける
+CREATE TABLE IF NOT EXISTS site_aliases (
+    host TEXT PRIMARY KEY,
+    site_id INTEGER NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (site_id) REFERENCES sites(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_site_aliases_site_id ON site_aliases(site_id);
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_articles_updated_at;
+DROP INDEX IF EXISTS idx_articles_created_at;
+DROP INDEX IF EXISTS idx_articles_site_id;
+DROP INDEX IF EXISTS idx_article_tags_tag_id;
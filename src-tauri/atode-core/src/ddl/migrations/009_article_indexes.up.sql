@@ -0,0 +1,9 @@
+-- 記事一覧の絞り込み・並び替え用インデックス
+-- article_tags の主キーは (article_id, tag_id) なので、タグからの逆引きには別途インデックスが要る
+CREATE INDEX IF NOT EXISTS idx_article_tags_tag_id ON article_tags(tag_id);
+CREATE INDEX IF NOT EXISTS idx_articles_site_id ON articles(site_id);
+
+-- 一覧は常にゴミ箱外（deleted_at IS NULL）で絞るため、日時は deleted_at との複合にして
+-- 並び替えをインデックス順のまま行えるようにする
+CREATE INDEX IF NOT EXISTS idx_articles_created_at ON articles(deleted_at, created_at);
+CREATE INDEX IF NOT EXISTS idx_articles_updated_at ON articles(deleted_at, updated_at);
@@ -0,0 +1,6 @@
+-- 同じページの別表記（http/https・末尾スラッシュ・youtu.be・AMP版（AMPと分かるページのみ）・m.ホストなど）を1つにまとめるキー
+-- url は保存したときのURLのまま残し、重複の判定には canonical_url を使う
+-- 既存の記事のキーは起動時にアプリ側で埋める（重複していた記事は NULL のまま残り、統合で解消する）
+ALTER TABLE articles ADD COLUMN canonical_url TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_articles_canonical_url ON articles(canonical_url);
@@ -0,0 +1,135 @@
+//! Atode の記事管理ロジック（Tauri・Windows APIに依存しない）
+//!
+//! アプリ本体・CLI・スクリプトから同じ`atode.db`を同じ規則で扱うためのライブラリ。
+//! 記事の保存・編集・検索は [`ArticleStore`] を通す
+//! （データベース: [`SqliteStore`]、メモリ上: [`MemoryStore`]）
+
+// エラーは`Result<_, String>`のメッセージで説明する（画面・CLIにそのまま出す）
+#![allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+
+use rusqlite::Connection;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// 処理ログ（保存・統合などの経過）を出すか。CLIは標準出力を結果だけにするため止める
+static LOGGING: AtomicBool = AtomicBool::new(true);
+
+/// 処理ログの出力を切り替える（既定は出す）
+pub fn set_logging(enabled: bool) {
+    LOGGING.store(enabled, Ordering::Relaxed);
+}
+
+// 処理ログ（標準出力）
+macro_rules! log {
+    ($($arg:tt)*) => {
+        if $crate::LOGGING.load(::std::sync::atomic::Ordering::Relaxed) {
+            println!($($arg)*);
+        }
+    };
+}
+
+// 処理ログ（警告・取り消しなど。標準エラー）
+macro_rules! log_error {
+    ($($arg:tt)*) => {
+        if $crate::LOGGING.load(::std::sync::atomic::Ordering::Relaxed) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// 記事の保存・編集・検索（SQLite）
+pub mod articles;
+pub use articles::{
+    ArticlePage, ArticleWithDetails, SaveArticleRequest, SearchFilters, SortKey, SortOrder,
+    TagCount, UpdateResult,
+};
+
+// 記事の保存先（SQLite・メモリ上）
+pub mod store;
+pub use store::{ArticleStore, SqliteStore};
+pub mod memory_store;
+pub use memory_store::MemoryStore;
+
+// 書き込み操作のトランザクション（エラーならすべてロールバック）
+pub mod repository;
+
+// スキーママイグレーション
+pub mod migration;
+
+// ルールファイルによる自動タグ付け
+pub mod auto_tag;
+
+// URL正規化のルール（サイトごとに残すクエリパラメータ）
+pub mod url_rules;
+
+// 保存済みの記事を再保存したときの扱い（タグの追加・タイトルの保持）
+pub mod resave;
+
+// 日時の変換・相対指定（last 7 days など）の解釈
+pub mod dates;
+
+// 記事検索（全文検索）
+pub mod search;
+
+// 読書ステータス（inbox / reading / done / reference）
+pub mod status;
+
+// 記事ごとのメモ
+pub mod notes;
+
+// ゴミ箱（論理削除）
+pub mod trash;
+
+// URLの正規形による重複の検出・統合
+pub mod canonical;
+
+// タグ階層・タグ名の正規化と別名
+pub mod tags;
+
+// タグの共起（関連タグ・共起グラフ）
+pub mod tag_graph;
+
+// サイト管理・ホスト名の別名
+pub mod sites;
+
+// Public Suffix List によるサイトの特定（登録可能ドメイン）
+pub mod public_suffix;
+
+// 検索式（tag:rust (tag:async OR tag:tokio) -site:qiita など）
+pub mod query;
+
+// 保存した検索（スマートフォルダ）
+pub mod saved_searches;
+
+// 読書状況の統計
+pub mod statistics;
+
+// 設定ファイル・ブラウザ起動（アプリ本体・CLIで共通）
+pub mod config;
+pub use config::Config;
+
+/// データベースを開き、スキーマを最新化する
+pub fn init_database(db_path: &str) -> Result<Connection, String> {
+    log!("📂 データベースパス: {db_path}");
+    let mut conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+
+    // バージョン管理されたマイグレーションでスキーマを最新化
+    let version = migration::migrate(&mut conn)?;
+    log!("🗄️ スキーマバージョン: v{version}");
+
+    // 正規化ルール導入前に作られたタグ名を揃える
+    let normalized = repository::write(&mut conn, tags::normalize_existing_tags)?;
+    if normalized > 0 {
+        log!("🏷️ タグ名を正規化: {normalized}件");
+    }
+
+    // 正規形の導入前に保存された記事のキーを埋める
+    let filled = repository::write(&mut conn, canonical::backfill_canonical_urls)?;
+    if filled > 0 {
+        log!("🔗 URLの正規形を設定: {filled}件");
+    }
+
+    conn.execute("PRAGMA foreign_keys = ON;", [])
+        .map_err(|e| e.to_string())?;
+
+    Ok(conn)
+}
@@ -0,0 +1,676 @@
+use chrono::{DateTime, SubsecRound, Utc};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use url::Url;
+
+use crate::articles::{
+    resolve_sort, ArticlePage, ArticleWithDetails, SaveArticleRequest, SearchFilters, SortKey,
+    SortOrder, TagCount, UpdateResult, RANDOM_MODULUS, RANDOM_MULTIPLIER,
+};
+use crate::canonical::{self, DuplicateArticle};
+use crate::query::{self, Expr, QueryError};
+use crate::resave::{ResavePolicy, SaveResult, TagPolicy, TitlePolicy};
+use crate::store::ArticleStore;
+use crate::url_rules::UrlRules;
+use crate::{dates, public_suffix, search, tags};
+
+// 新規記事の読書ステータス（SQLiteの初期データと同じ）
+const INITIAL_STATUS: &str = "inbox";
+
+/// メモリ上の記事データ（テストやスクリプトの一時的な作業用。プロセス終了で消える）
+///
+/// URLの正規化・重複の判定・再保存の扱い・検索条件は`SqliteStore`と同じ。
+/// タグやサイトの別名・タグ階層（`include_descendants`）は扱わず、
+/// 関連度順は更新日時順になる（全文検索の索引が無いため）
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    url_rules: UrlRules,
+    resave_policy: ResavePolicy,
+    articles: Vec<StoredArticle>,
+    last_id: i64,
+}
+
+#[derive(Debug, Clone)]
+struct StoredArticle {
+    id: i64,
+    url: String,
+    canonical_url: String,
+    title: String,
+    site_name: String,
+    tags: Vec<String>,
+    status: String,
+    status_changed_at: DateTime<Utc>,
+    notes: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    deleted: bool,
+}
+
+impl MemoryStore {
+    pub fn new(url_rules: UrlRules, resave_policy: ResavePolicy) -> Self {
+        Self {
+            url_rules,
+            resave_policy,
+            ..Self::default()
+        }
+    }
+
+    fn live_article(&self, id: i64) -> Option<&StoredArticle> {
+        self.articles
+            .iter()
+            .find(|article| article.id == id && !article.deleted)
+    }
+
+    // URLの正規化・正規形・サイト名（保存時と編集時で同じ扱いにする）
+    fn locate(&self, url: &str) -> Result<(String, String, String), String> {
+        let normalized_url = self.url_rules.normalize(url);
+        let canonical_url = canonical::canonical_url(&normalized_url);
+        let parsed_url = Url::parse(&normalized_url).map_err(|e| e.to_string())?;
+        let site_name = public_suffix::site_identity(parsed_url.host_str().unwrap_or("")).name;
+        Ok((normalized_url, canonical_url, site_name))
+    }
+}
+
+impl ArticleStore for MemoryStore {
+    fn save_article(&mut self, request: SaveArticleRequest) -> Result<SaveResult, String> {
+        let (normalized_url, canonical_url, site_name) = self.locate(&request.url)?;
+        let requested_tags = resolve_tags(request.tags.as_deref());
+        let now = now();
+        let resave = &self.resave_policy;
+
+        if let Some(article) = self
+            .articles
+            .iter_mut()
+            .find(|article| article.canonical_url == canonical_url || article.url == normalized_url)
+        {
+            let title_changed =
+                resave.title == TitlePolicy::Replace && article.title != request.title;
+            if title_changed {
+                article.title = request.title;
+            }
+            let restored = std::mem::take(&mut article.deleted);
+            article.updated_at = now;
+
+            let mut removed_tags = Vec::new();
+            if resave.tags == TagPolicy::Replace {
+                article.tags.retain(|tag| {
+                    let keep = requested_tags.contains(tag);
+                    if !keep {
+                        removed_tags.push(tag.clone());
+                    }
+                    keep
+                });
+            }
+            let added_tags = add_tags(&mut article.tags, &requested_tags);
+            return Ok(SaveResult::resaved(
+                article.id,
+                added_tags,
+                removed_tags,
+                title_changed,
+                restored,
+            ));
+        }
+
+        self.last_id += 1;
+        let mut article = StoredArticle {
+            id: self.last_id,
+            url: normalized_url,
+            canonical_url,
+            title: request.title,
+            site_name,
+            tags: Vec::new(),
+            status: INITIAL_STATUS.to_string(),
+            status_changed_at: now,
+            notes: String::new(),
+            created_at: now,
+            updated_at: now,
+            deleted: false,
+        };
+        let added_tags = add_tags(&mut article.tags, &requested_tags);
+        self.articles.push(article);
+        Ok(SaveResult::created(self.last_id, added_tags))
+    }
+
+    fn update_article(
+        &mut self,
+        id: i64,
+        request: &SaveArticleRequest,
+        merge: bool,
+    ) -> Result<UpdateResult, String> {
+        if !self.articles.iter().any(|article| article.id == id) {
+            return Err(format!("記事が見つかりません (ID: {id})"));
+        }
+        let (normalized_url, canonical_url, site_name) = self.locate(request.url.trim())?;
+
+        // URLが重なる記事の確認（統合しないなら何も変えずに返す）
+        let conflict = self
+            .articles
+            .iter()
+            .find(|article| {
+                article.id != id
+                    && (article.canonical_url == canonical_url || article.url == normalized_url)
+            })
+            .cloned();
+        if let Some(other) = conflict.as_ref().filter(|_| !merge) {
+            return Ok(UpdateResult::Conflict {
+                article: DuplicateArticle {
+                    id: other.id,
+                    url: other.url.clone(),
+                    title: other.title.clone(),
+                    created_at: other.created_at,
+                    deleted: other.deleted,
+                },
+            });
+        }
+
+        if let Some(other) = &conflict {
+            self.articles.retain(|article| article.id != other.id);
+        }
+        let article = self
+            .articles
+            .iter_mut()
+            .find(|article| article.id == id)
+            .ok_or_else(|| format!("記事が見つかりません (ID: {id})"))?;
+
+        // タグは指定したものに置き換え、重なる記事のタグ・メモ・日時・ステータスを引き継ぐ
+        article.tags.clear();
+        add_tags(&mut article.tags, &resolve_tags(request.tags.as_deref()));
+        if let Some(other) = &conflict {
+            add_tags(&mut article.tags, &other.tags);
+            article.notes = canonical::merge_notes(&article.notes, &other.notes);
+            article.created_at = article.created_at.min(other.created_at);
+            article.updated_at = article.updated_at.max(other.updated_at);
+            article.deleted = article.deleted && other.deleted;
+            if other.status_changed_at > article.status_changed_at {
+                article.status.clone_from(&other.status);
+                article.status_changed_at = other.status_changed_at;
+            }
+        }
+
+        article.title.clone_from(&request.title);
+        article.url = normalized_url;
+        article.canonical_url = canonical_url;
+        article.site_name = site_name;
+        article.updated_at = now();
+
+        Ok(
+            conflict.map_or(UpdateResult::Updated, |other| UpdateResult::Merged {
+                merged_id: other.id,
+            }),
+        )
+    }
+
+    fn delete_article(&mut self, id: i64) -> Result<(), String> {
+        let article = self
+            .articles
+            .iter_mut()
+            .find(|article| article.id == id && !article.deleted)
+            .ok_or_else(|| format!("記事が見つかりません (ID: {id})"))?;
+        article.deleted = true;
+        Ok(())
+    }
+
+    fn delete_articles(&mut self, ids: &[i64]) -> Result<(), String> {
+        // SQLiteと同じく、1件でも移せなければ何も移さない
+        for (index, &id) in ids.iter().enumerate() {
+            if self.live_article(id).is_none() || ids[..index].contains(&id) {
+                return Err(format!("記事が見つかりません (ID: {id})"));
+            }
+        }
+        ids.iter().try_for_each(|&id| self.delete_article(id))
+    }
+
+    fn get_article(&self, id: i64) -> Result<Option<ArticleWithDetails>, String> {
+        Ok(self.live_article(id).map(|article| details(article, None)))
+    }
+
+    fn search_articles(&self, filters: &SearchFilters) -> Result<ArticlePage, String> {
+        let keywords = filters.text.as_deref().and_then(search::parse_keywords);
+        let expr = filters
+            .query
+            .as_deref()
+            .map(query::parse)
+            .transpose()
+            .map_err(|e| e.to_string())?
+            .flatten();
+        let tag_names: Vec<String> = filters
+            .tag_query
+            .as_deref()
+            .map(|tag_query| tag_query.split(',').map(tags::normalize_tag_name).collect())
+            .unwrap_or_default();
+        let resolve = |value: &Option<String>| {
+            value
+                .as_deref()
+                .filter(|v| !v.trim().is_empty())
+                .map(dates::resolve)
+                .transpose()
+        };
+        let (created_after, created_before) = (
+            resolve(&filters.created_after)?,
+            resolve(&filters.created_before)?,
+        );
+        let (updated_after, updated_before) = (
+            resolve(&filters.updated_after)?,
+            resolve(&filters.updated_before)?,
+        );
+
+        let mut matched = Vec::new();
+        for article in self.articles.iter().filter(|article| !article.deleted) {
+            let keep = filters
+                .site
+                .as_deref()
+                .is_none_or(|site| contains_ignore_case(&article.site_name, site))
+                && tag_names.iter().all(|tag| article.tags.contains(tag))
+                && filters
+                    .status
+                    .as_deref()
+                    .is_none_or(|status| article.status.eq_ignore_ascii_case(status.trim()))
+                && created_after.is_none_or(|at| article.created_at >= at)
+                && created_before.is_none_or(|at| article.created_at < at)
+                && updated_after.is_none_or(|at| article.updated_at >= at)
+                && updated_before.is_none_or(|at| article.updated_at < at)
+                && keywords.as_ref().is_none_or(|keywords| {
+                    keywords
+                        .terms
+                        .iter()
+                        .all(|term| matches_text(article, term))
+                });
+            if keep
+                && expr
+                    .as_ref()
+                    .map_or(Ok(true), |expr| evaluate(expr, article))?
+            {
+                matched.push(article);
+            }
+        }
+
+        sort_articles(&mut matched, filters, keywords.is_some());
+
+        let total = u32::try_from(matched.len()).map_err(|e| e.to_string())?;
+        let offset = filters.offset.unwrap_or(0);
+        let articles = matched
+            .into_iter()
+            .skip(offset as usize)
+            .take(filters.limit.map_or(usize::MAX, |limit| limit as usize))
+            .map(|article| {
+                let snippet = keywords.as_ref().and_then(|keywords| {
+                    search::snippet(
+                        &article.title,
+                        &article.url,
+                        &article.notes,
+                        &keywords.terms,
+                    )
+                });
+                details(article, snippet)
+            })
+            .collect();
+
+        Ok(ArticlePage {
+            articles,
+            total,
+            offset,
+        })
+    }
+
+    fn popular_tags(&self, limit: usize, status: Option<&str>) -> Result<Vec<TagCount>, String> {
+        let mut counts: HashMap<&str, u32> = HashMap::new();
+        for article in self.articles.iter().filter(|article| {
+            !article.deleted
+                && status.is_none_or(|status| article.status.eq_ignore_ascii_case(status.trim()))
+        }) {
+            for tag in article
+                .tags
+                .iter()
+                .filter(|tag| *tag != tags::EXCLUDED_FROM_STATS)
+            {
+                *counts.entry(tag).or_default() += 1;
+            }
+        }
+
+        let mut result: Vec<TagCount> = counts
+            .into_iter()
+            .map(|(tag, count)| TagCount {
+                tag: tag.to_string(),
+                count,
+            })
+            .collect();
+        result.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+        result.truncate(limit);
+        Ok(result)
+    }
+}
+
+// 日時はSQLiteの CURRENT_TIMESTAMP と同じく秒単位
+fn now() -> DateTime<Utc> {
+    Utc::now().trunc_subsecs(0)
+}
+
+// 保存するタグ名（正規化し、空と重複は除く）
+fn resolve_tags(tags: Option<&str>) -> Vec<String> {
+    let mut resolved = Vec::new();
+    add_tags(
+        &mut resolved,
+        &tags
+            .unwrap_or("")
+            .split(',')
+            .map(tags::normalize_tag_name)
+            .filter(|tag| !tag.is_empty())
+            .collect::<Vec<_>>(),
+    );
+    resolved
+}
+
+// 付いていないタグを付け、付けたタグ名を返す
+fn add_tags(current: &mut Vec<String>, tags: &[String]) -> Vec<String> {
+    let mut added = Vec::new();
+    for tag in tags {
+        if !current.contains(tag) {
+            current.push(tag.clone());
+            added.push(tag.clone());
+        }
+    }
+    added
+}
+
+fn details(article: &StoredArticle, snippet: Option<String>) -> ArticleWithDetails {
+    let mut tags = article.tags.clone();
+    tags.sort_unstable();
+    ArticleWithDetails {
+        id: article.id,
+        url: article.url.clone(),
+        title: article.title.clone(),
+        site_name: Some(article.site_name.clone()),
+        tags,
+        status: Some(article.status.clone()),
+        status_changed_at: Some(article.status_changed_at),
+        notes: article.notes.clone(),
+        created_at: article.created_at,
+        updated_at: article.updated_at,
+        snippet,
+    }
+}
+
+// 大文字小文字を区別しない部分一致（SQLiteの LIKE と同じ扱い）
+fn contains_ignore_case(text: &str, pattern: &str) -> bool {
+    text.to_lowercase().contains(&pattern.to_lowercase())
+}
+
+// タイトル・URL・メモのいずれかに含まれるか
+fn matches_text(article: &StoredArticle, term: &str) -> bool {
+    [&article.title, &article.url, &article.notes]
+        .iter()
+        .any(|text| contains_ignore_case(text, term))
+}
+
+// 検索式の評価（SQLへの変換と同じ意味になるようにする）
+fn evaluate(expr: &Expr, article: &StoredArticle) -> Result<bool, String> {
+    match expr {
+        Expr::And(exprs) => {
+            for expr in exprs {
+                if !evaluate(expr, article)? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+        Expr::Or(exprs) => {
+            for expr in exprs {
+                if evaluate(expr, article)? {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        }
+        Expr::Not(expr) => Ok(!evaluate(expr, article)?),
+        Expr::Term {
+            field,
+            comparison,
+            value,
+            position,
+        } => {
+            let matched = match field.as_deref() {
+                None => matches_text(article, value),
+                Some("tag") => article.tags.contains(&tags::normalize_tag_name(value)),
+                Some("site") => contains_ignore_case(&article.site_name, value),
+                Some("title") => contains_ignore_case(&article.title, value),
+                Some("url") => contains_ignore_case(&article.url, value),
+                Some("notes") => contains_ignore_case(&article.notes, value),
+                Some("status") => article.status.eq_ignore_ascii_case(value),
+                Some(field @ ("saved" | "updated")) => {
+                    let at = if field == "saved" {
+                        article.created_at
+                    } else {
+                        article.updated_at
+                    };
+                    let (start, end) = query::date_range(*comparison, value, *position)
+                        .map_err(|e| e.to_string())?;
+                    start.is_none_or(|start| at >= start) && end.is_none_or(|end| at < end)
+                }
+                Some(field) => {
+                    return Err(QueryError {
+                        position: *position,
+                        message: format!("不明なフィールド「{field}」"),
+                    }
+                    .to_string())
+                }
+            };
+            Ok(matched)
+        }
+    }
+}
+
+// 並び順（SQLiteの order_clause と同じ。関連度順は更新日時順）
+fn sort_articles(articles: &mut [&StoredArticle], filters: &SearchFilters, has_keywords: bool) {
+    let (sort, order) = resolve_sort(filters, has_keywords);
+    let directed = |ordering: Ordering| match order {
+        SortOrder::Asc => ordering,
+        SortOrder::Desc => ordering.reverse(),
+    };
+    let newest_first =
+        |a: &StoredArticle, b: &StoredArticle| (b.updated_at, b.id).cmp(&(a.updated_at, a.id));
+
+    match sort {
+        SortKey::Created => {
+            articles.sort_by(|a, b| directed((a.created_at, a.id).cmp(&(b.created_at, b.id))));
+        }
+        SortKey::Updated => {
+            articles.sort_by(|a, b| directed((a.updated_at, a.id).cmp(&(b.updated_at, b.id))));
+        }
+        SortKey::Title => articles.sort_by(|a, b| {
+            directed(
+                a.title
+                    .to_lowercase()
+                    .cmp(&b.title.to_lowercase())
+                    .then(a.id.cmp(&b.id)),
+            )
+        }),
+        SortKey::Site => articles.sort_by(|a, b| {
+            directed(a.site_name.to_lowercase().cmp(&b.site_name.to_lowercase()))
+                .then_with(|| newest_first(a, b))
+        }),
+        // シードが同じなら同じ順序（SQLiteと同じ式）
+        SortKey::Random => {
+            let seed = filters
+                .seed
+                .unwrap_or_else(|| Utc::now().timestamp_subsec_nanos());
+            let seed = u64::from(seed) % RANDOM_MODULUS;
+            articles.sort_by_key(|article| {
+                let id = article.id.unsigned_abs();
+                (
+                    (id + seed) % RANDOM_MODULUS * RANDOM_MULTIPLIER % RANDOM_MODULUS,
+                    article.id,
+                )
+            });
+        }
+        SortKey::Relevance => articles.sort_by(|a, b| newest_first(a, b)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init_database;
+    use crate::store::SqliteStore;
+
+    fn request(url: &str, title: &str, tags: &str) -> SaveArticleRequest {
+        SaveArticleRequest {
+            url: url.to_string(),
+            title: title.to_string(),
+            tags: Some(tags.to_string()),
+        }
+    }
+
+    fn ids(store: &dyn ArticleStore, filters: &SearchFilters) -> Vec<i64> {
+        store
+            .search_articles(filters)
+            .unwrap()
+            .articles
+            .iter()
+            .map(|article| article.id)
+            .collect()
+    }
+
+    // 同じ操作をした2つの保存先で結果が揃うこと
+    #[test]
+    fn test_memory_store_matches_sqlite_store() {
+        let sqlite = SqliteStore::new(
+            init_database(":memory:").unwrap(),
+            UrlRules::default(),
+            ResavePolicy::default(),
+        );
+        let memory = MemoryStore::default();
+        let mut stores: [Box<dyn ArticleStore>; 2] = [Box::new(sqlite), Box::new(memory)];
+
+        for store in &mut stores {
+            let saved = [
+                request(
+                    "https://www.example.com/a?utm_source=x",
+                    "Rust async",
+                    "Rust, async",
+                ),
+                request("https://qiita.com/b", "Python tips", "python"),
+                request("https://zenn.dev/c", "Go 入門", "go"),
+            ]
+            .map(|request| store.save_article(request).unwrap().article_id);
+            assert_eq!(saved, [1, 2, 3]);
+
+            // 表記違いの再保存はタグを追加するだけ
+            let result = store
+                .save_article(request("http://example.com/a/", "renamed", "tokio"))
+                .unwrap();
+            assert_eq!(
+                (result.article_id, result.added_tags),
+                (1, vec!["tokio".into()])
+            );
+
+            // URLが重なる編集は確認を返し、merge なら統合する
+            let collide = request("https://zenn.dev/c", "Go", "golang");
+            assert!(matches!(
+                store.update_article(2, &collide, false).unwrap(),
+                UpdateResult::Conflict { .. }
+            ));
+            assert!(matches!(
+                store.update_article(2, &collide, true).unwrap(),
+                UpdateResult::Merged { merged_id: 3 }
+            ));
+            store.delete_article(1).unwrap();
+            assert!(store.delete_article(1).is_err());
+            assert!(store.get_article(1).unwrap().is_none());
+        }
+
+        for store in &stores {
+            let article = store.get_article(2).unwrap().unwrap();
+            assert_eq!(article.url, "https://zenn.dev/c");
+            assert_eq!(article.tags, ["go", "golang"]);
+            assert_eq!(article.site_name.as_deref(), Some("zenn.dev"));
+            assert_eq!(article.status.as_deref(), Some("inbox"));
+
+            let all = SearchFilters::default();
+            assert_eq!(ids(store.as_ref(), &all), [2]);
+            let filters = SearchFilters {
+                query: Some("tag:golang -site:qiita".into()),
+                ..SearchFilters::default()
+            };
+            assert_eq!(ids(store.as_ref(), &filters), [2]);
+            let filters = SearchFilters {
+                text: Some("rust".into()),
+                ..SearchFilters::default()
+            };
+            assert!(ids(store.as_ref(), &filters).is_empty());
+            let tags = store.popular_tags(10, Some("inbox")).unwrap();
+            assert_eq!(tags.len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_memory_store_filters_and_sort() {
+        let mut store = MemoryStore::default();
+        for (url, title, tags) in [
+            ("https://example.com/a", "Beta", "rust"),
+            ("https://qiita.com/b", "alpha", "rust, async"),
+            ("https://zenn.dev/c", "Gamma", "python"),
+        ] {
+            store.save_article(request(url, title, tags)).unwrap();
+        }
+
+        let search = |filters: SearchFilters| ids(&store, &filters);
+        assert_eq!(
+            search(SearchFilters {
+                tag_query: Some("Rust, ASYNC".into()),
+                ..SearchFilters::default()
+            }),
+            [2]
+        );
+        assert_eq!(
+            search(SearchFilters {
+                site: Some("QIITA".into()),
+                ..SearchFilters::default()
+            }),
+            [2]
+        );
+        assert_eq!(
+            search(SearchFilters {
+                query: Some("tag:rust OR title:gamma".into()),
+                sort: Some(SortKey::Title),
+                ..SearchFilters::default()
+            }),
+            [2, 1, 3]
+        );
+        assert_eq!(
+            search(SearchFilters {
+                sort: Some(SortKey::Created),
+                order: Some(SortOrder::Asc),
+                limit: Some(2),
+                offset: Some(1),
+                ..SearchFilters::default()
+            }),
+            [2, 3]
+        );
+        assert_eq!(
+            search(SearchFilters {
+                updated_after: Some("last 1 days".into()),
+                ..SearchFilters::default()
+            })
+            .len(),
+            3
+        );
+        assert!(store
+            .search_articles(&SearchFilters {
+                query: Some("saved:someday".into()),
+                ..SearchFilters::default()
+            })
+            .is_err());
+
+        let page = store
+            .search_articles(&SearchFilters {
+                text: Some("alp".into()),
+                ..SearchFilters::default()
+            })
+            .unwrap();
+        assert_eq!(page.total, 1);
+        assert_eq!(
+            page.articles[0].snippet.as_deref(),
+            search::highlight("alpha", &["alp".into()]).as_deref()
+        );
+    }
+}
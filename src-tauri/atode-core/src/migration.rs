@@ -0,0 +1,334 @@
+use rusqlite::{params, Connection};
+
+/// スキーマのマイグレーション1件分
+/// `up`/`down`はそれぞれ1トランザクション内で`execute_batch`される
+struct Migration {
+    version: u32,
+    name: &'static str,
+    up: &'static str,
+    down: &'static str,
+}
+
+/// マイグレーション定義（バージョン昇順）
+/// リリース済みのマイグレーションは書き換えず、変更は必ず新しいバージョンとして追加する
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        // v1.1までの`CREATE TABLE IF NOT EXISTS`によるスキーマ。既存DBに対しては何もしない
+        version: 1,
+        name: "initial_schema",
+        up: concat!(
+            include_str!("ddl/001_create_sites.sql"),
+            "\n",
+            include_str!("ddl/002_create_articles.sql"),
+            "\n",
+            include_str!("ddl/003_create_tags.sql"),
+            "\n",
+            include_str!("ddl/004_create_article_tags.sql"),
+        ),
+        down: include_str!("ddl/migrations/001_initial_schema.down.sql"),
+    },
+    Migration {
+        version: 2,
+        name: "articles_fts",
+        up: include_str!("ddl/migrations/002_articles_fts.up.sql"),
+        down: include_str!("ddl/migrations/002_articles_fts.down.sql"),
+    },
+    Migration {
+        version: 3,
+        name: "articles_fts_trigram",
+        up: include_str!("ddl/migrations/003_articles_fts_trigram.up.sql"),
+        down: include_str!("ddl/migrations/003_articles_fts_trigram.down.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "reading_status",
+        up: include_str!("ddl/migrations/004_reading_status.up.sql"),
+        down: include_str!("ddl/migrations/004_reading_status.down.sql"),
+    },
+    Migration {
+        version: 5,
+        name: "article_notes",
+        up: include_str!("ddl/migrations/005_article_notes.up.sql"),
+        down: include_str!("ddl/migrations/005_article_notes.down.sql"),
+    },
+    Migration {
+        version: 6,
+        name: "article_trash",
+        up: include_str!("ddl/migrations/006_article_trash.up.sql"),
+        down: include_str!("ddl/migrations/006_article_trash.down.sql"),
+    },
+    Migration {
+        version: 7,
+        name: "tag_aliases",
+        up: include_str!("ddl/migrations/007_tag_aliases.up.sql"),
+        down: include_str!("ddl/migrations/007_tag_aliases.down.sql"),
+    },
+    Migration {
+        version: 8,
+        name: "site_aliases",
+        up: include_str!("ddl/migrations/008_site_aliases.up.sql"),
+        down: include_str!("ddl/migrations/008_site_aliases.down.sql"),
+    },
+    Migration {
+        version: 9,
+        name: "article_indexes",
+        up: include_str!("ddl/migrations/009_article_indexes.up.sql"),
+        down: include_str!("ddl/migrations/009_article_indexes.down.sql"),
+    },
+    Migration {
+        version: 10,
+        name: "saved_searches",
+        up: include_str!("ddl/migrations/010_saved_searches.up.sql"),
+        down: include_str!("ddl/migrations/010_saved_searches.down.sql"),
+    },
+    Migration {
+        version: 11,
+        name: "canonical_urls",
+        up: include_str!("ddl/migrations/011_canonical_urls.up.sql"),
+        down: include_str!("ddl/migrations/011_canonical_urls.down.sql"),
+    },
+];
+
+/// このアプリが扱える最新のスキーマバージョン
+pub fn latest_version() -> u32 {
+    MIGRATIONS.last().map_or(0, |m| m.version)
+}
+
+/// 適用済みのスキーマバージョンを取得（未管理のDBは0）
+pub fn current_version(conn: &Connection) -> Result<u32, String> {
+    ensure_version_table(conn)?;
+    conn.query_row(
+        "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+        [],
+        |row| row.get(0),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// 最新バージョンまでマイグレーションを適用し、適用後のバージョンを返す
+pub fn migrate(conn: &mut Connection) -> Result<u32, String> {
+    migrate_to(conn, latest_version())
+}
+
+/// 指定バージョンまでマイグレーションを適用（現在より古ければ`down`で巻き戻す）
+pub fn migrate_to(conn: &mut Connection, target: u32) -> Result<u32, String> {
+    let latest = latest_version();
+    if target > latest {
+        return Err(format!(
+            "未知のスキーマバージョンです: v{target}（対応しているのはv{latest}まで）"
+        ));
+    }
+
+    let current = current_version(conn)?;
+    if current > latest {
+        return Err(format!(
+            "データベースのスキーマ(v{current})がこのアプリの対応バージョン(v{latest})より新しいため開けません。アプリを更新してください"
+        ));
+    }
+
+    // テーブル再作成を伴うマイグレーションのため外部キー制約を一時的に無効化
+    // （トランザクション内では切り替えられないので、ここで切り替える）
+    conn.execute_batch("PRAGMA foreign_keys = OFF;")
+        .map_err(|e| e.to_string())?;
+
+    let result = if target >= current {
+        MIGRATIONS
+            .iter()
+            .filter(|m| m.version > current && m.version <= target)
+            .try_for_each(|m| apply(conn, m, Direction::Up))
+    } else {
+        MIGRATIONS
+            .iter()
+            .rev()
+            .filter(|m| m.version <= current && m.version > target)
+            .try_for_each(|m| apply(conn, m, Direction::Down))
+    };
+
+    conn.execute_batch("PRAGMA foreign_keys = ON;")
+        .map_err(|e| e.to_string())?;
+
+    result?;
+    current_version(conn)
+}
+
+#[derive(Clone, Copy)]
+enum Direction {
+    Up,
+    Down,
+}
+
+// 1マイグレーションを1トランザクションで適用（失敗時は全て巻き戻る）
+fn apply(conn: &mut Connection, migration: &Migration, direction: Direction) -> Result<(), String> {
+    let label = format!("v{:03}_{}", migration.version, migration.name);
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    match direction {
+        Direction::Up => {
+            log!("⬆️ マイグレーション適用: {label}");
+            tx.execute_batch(migration.up)
+                .map_err(|e| format!("マイグレーション {label} の適用に失敗: {e}"))?;
+            tx.execute(
+                "INSERT INTO schema_version (version, name) VALUES (?, ?)",
+                params![migration.version, migration.name],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        Direction::Down => {
+            log!("⬇️ マイグレーション巻き戻し: {label}");
+            tx.execute_batch(migration.down)
+                .map_err(|e| format!("マイグレーション {label} の巻き戻しに失敗: {e}"))?;
+            tx.execute(
+                "DELETE FROM schema_version WHERE version = ?",
+                params![migration.version],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+    }
+
+    // 外部キー制約を無効化しているので、コミット前に整合性を確認する
+    let violations: i64 = tx
+        .query_row("SELECT COUNT(*) FROM pragma_foreign_key_check", [], |row| {
+            row.get(0)
+        })
+        .map_err(|e| e.to_string())?;
+    if violations > 0 {
+        return Err(format!(
+            "マイグレーション {label} 後に外部キー違反が{violations}件見つかりました"
+        ));
+    }
+
+    tx.commit().map_err(|e| e.to_string())
+}
+
+fn ensure_version_table(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        );",
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // v1.1のアプリが作成したDB（データ入り、schema_versionなし）
+    fn v1_1_fixture() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(include_str!("../tests/fixtures/atode_v1_1.sql"))
+            .unwrap();
+        conn
+    }
+
+    fn table_exists(conn: &Connection, name: &str) -> bool {
+        conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE name = ?",
+            [name],
+            |row| row.get::<_, i64>(0),
+        )
+        .unwrap()
+            > 0
+    }
+
+    #[test]
+    fn test_migrations_are_ordered() {
+        for (i, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version as usize, i + 1, "{}", migration.name);
+        }
+    }
+
+    #[test]
+    fn test_upgrade_v1_1_database() {
+        let mut conn = v1_1_fixture();
+        assert_eq!(current_version(&conn).unwrap(), 0);
+
+        assert_eq!(migrate(&mut conn).unwrap(), latest_version());
+
+        // 既存データはそのまま残る
+        let articles: i64 = conn
+            .query_row("SELECT COUNT(*) FROM articles", [], |row| row.get(0))
+            .unwrap();
+        let links: i64 = conn
+            .query_row("SELECT COUNT(*) FROM article_tags", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(articles, 3);
+        assert_eq!(links, 7);
+
+        let title: String = conn
+            .query_row("SELECT title FROM articles WHERE id = 2", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(title, "Rustの所有権を図解で理解する");
+
+        // 既存記事が全文検索インデックスへ取り込まれている
+        let hits: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM articles_fts WHERE articles_fts MATCH 'tauri'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(hits, 1);
+
+        // 既存記事はすべて inbox から開始
+        let inbox: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM articles a JOIN statuses st ON a.status_id = st.id
+                 WHERE st.name = 'inbox' AND a.status_changed_at = a.created_at",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(inbox, 3);
+
+        // 再実行しても何も起きない
+        assert_eq!(migrate(&mut conn).unwrap(), latest_version());
+    }
+
+    #[test]
+    fn test_migrate_down_and_up_again() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn).unwrap();
+        assert!(table_exists(&conn, "articles"));
+
+        assert_eq!(migrate_to(&mut conn, 0).unwrap(), 0);
+        assert!(!table_exists(&conn, "articles"));
+
+        assert_eq!(migrate(&mut conn).unwrap(), latest_version());
+        assert!(table_exists(&conn, "articles"));
+    }
+
+    #[test]
+    fn test_refuse_newer_database() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn).unwrap();
+        conn.execute(
+            "INSERT INTO schema_version (version, name) VALUES (?, 'from_the_future')",
+            [latest_version() + 1],
+        )
+        .unwrap();
+
+        assert!(migrate(&mut conn).is_err());
+    }
+
+    #[test]
+    fn test_failed_migration_rolls_back() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn).unwrap();
+
+        let broken = Migration {
+            version: latest_version() + 1,
+            name: "broken",
+            up: "CREATE TABLE half_done (id INTEGER); SELECT * FROM no_such_table;",
+            down: "",
+        };
+        assert!(apply(&mut conn, &broken, Direction::Up).is_err());
+
+        assert!(!table_exists(&conn, "half_done"));
+        assert_eq!(current_version(&conn).unwrap(), latest_version());
+    }
+}
@@ -0,0 +1,64 @@
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// 記事のメモ（Markdown）を取得
+pub fn get_notes(db: &Connection, article_id: i64) -> Result<String, String> {
+    db.query_row(
+        "SELECT notes FROM articles WHERE id = ?",
+        [article_id],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("記事が見つかりません (ID: {article_id})"))
+}
+
+/// 記事のメモを保存し、記事の更新日時を進める
+pub fn set_notes(db: &Connection, article_id: i64, notes: &str) -> Result<(), String> {
+    let rows = db
+        .execute(
+            "UPDATE articles SET notes = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+            params![notes, article_id],
+        )
+        .map_err(|e| e.to_string())?;
+    if rows == 0 {
+        return Err(format!("記事が見つかりません (ID: {article_id})"));
+    }
+
+    log!(
+        "メモ保存完了: ID {article_id} ({}文字)",
+        notes.chars().count()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::migration;
+
+    #[test]
+    fn test_set_notes_bumps_updated_at() {
+        let mut db = Connection::open_in_memory().unwrap();
+        migration::migrate(&mut db).unwrap();
+        db.execute(
+            "INSERT INTO articles (url, title, updated_at)
+             VALUES ('https://example.com/', 'Example', '2025-01-01 00:00:00')",
+            [],
+        )
+        .unwrap();
+
+        assert_eq!(get_notes(&db, 1).unwrap(), "");
+        set_notes(&db, 1, "# 要点\n- あとで試す").unwrap();
+        assert_eq!(get_notes(&db, 1).unwrap(), "# 要点\n- あとで試す");
+
+        let updated_at: String = db
+            .query_row("SELECT updated_at FROM articles WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_ne!(updated_at, "2025-01-01 00:00:00");
+
+        assert!(get_notes(&db, 2).is_err());
+        assert!(set_notes(&db, 2, "x").is_err());
+    }
+}
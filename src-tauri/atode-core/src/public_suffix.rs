@@ -0,0 +1,168 @@
+use std::collections::HashSet;
+use std::net::IpAddr;
+use std::sync::LazyLock;
+use url::Host;
+
+use crate::sites;
+
+// Public Suffix List のスナップショット（https://publicsuffix.org/list/public_suffix_list.dat）
+// 更新するときはファイルを差し替える
+const PUBLIC_SUFFIX_LIST: &str = include_str!("data/public_suffix_list.dat");
+
+static RULES: LazyLock<SuffixRules> = LazyLock::new(|| SuffixRules::parse(PUBLIC_SUFFIX_LIST));
+
+/// ホスト名から求めたサイトの識別情報
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiteIdentity {
+    /// サイト名（登録可能ドメイン。例: `example.co.uk`, `foo.github.io`）
+    /// IPアドレスや`localhost`などはホスト名のまま
+    pub name: String,
+    /// 自動タグ用のラベル（公開サフィックス直前のラベル。例: `example`, `foo`）
+    pub label: String,
+}
+
+/// ホスト名からサイトを特定する（`sites.name`と自動タグの両方でこれを使う）
+pub fn site_identity(host: &str) -> SiteIdentity {
+    let host = sites::normalize_host(host);
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+
+    let registrable = if host.parse::<IpAddr>().is_ok() {
+        None
+    } else {
+        registrable_domain(host)
+    };
+
+    registrable.map_or_else(
+        || SiteIdentity {
+            name: host.to_string(),
+            label: host.to_string(),
+        },
+        |name| {
+            let label = name.split('.').next().unwrap_or_default().to_string();
+            SiteIdentity { name, label }
+        },
+    )
+}
+
+/// 登録可能ドメイン（公開サフィックス + 1ラベル）。ホスト名自体が公開サフィックスなら`None`
+pub fn registrable_domain(host: &str) -> Option<String> {
+    let labels: Vec<&str> = host.split('.').collect();
+    if labels.iter().any(|label| label.is_empty()) {
+        return None;
+    }
+
+    let suffix_len = RULES.suffix_len(&labels);
+    (labels.len() > suffix_len).then(|| labels[labels.len() - suffix_len - 1..].join("."))
+}
+
+// 通常・ワイルドカード（`*.ck`）・例外（`!www.ck`）のルール
+// 国際化ドメインのルールはPunycodeにして持つ（URLのホスト名がPunycodeのため）
+#[derive(Debug, Default)]
+struct SuffixRules {
+    normal: HashSet<String>,
+    // `*.`を除いた親ドメイン
+    wildcard: HashSet<String>,
+    // `!`を除いたドメイン
+    exception: HashSet<String>,
+}
+
+impl SuffixRules {
+    fn parse(list: &str) -> Self {
+        let mut rules = Self::default();
+        for line in list.lines() {
+            let Some(rule) = line.split_whitespace().next() else {
+                continue;
+            };
+            if rule.starts_with("//") {
+                continue;
+            }
+
+            let (set, domain) = if let Some(domain) = rule.strip_prefix('!') {
+                (&mut rules.exception, domain)
+            } else if let Some(domain) = rule.strip_prefix("*.") {
+                (&mut rules.wildcard, domain)
+            } else {
+                (&mut rules.normal, rule)
+            };
+            if let Some(domain) = to_ascii(domain) {
+                set.insert(domain);
+            }
+        }
+        rules
+    }
+
+    // 公開サフィックスのラベル数（例外ルールが最優先、次に最長一致。どれにも一致しなければ1）
+    fn suffix_len(&self, labels: &[&str]) -> usize {
+        for start in 0..labels.len() {
+            let candidate = labels[start..].join(".");
+            if self.exception.contains(&candidate) {
+                return labels.len() - start - 1;
+            }
+            let parent = labels[start + 1..].join(".");
+            if self.normal.contains(&candidate)
+                || (start + 1 < labels.len() && self.wildcard.contains(&parent))
+            {
+                // 例外ルールはワイルドカードより長いので、ここより左で一致することはない
+                return labels.len() - start;
+            }
+        }
+        1
+    }
+}
+
+fn to_ascii(domain: &str) -> Option<String> {
+    let domain = domain.to_lowercase();
+    if domain.is_ascii() {
+        return Some(domain);
+    }
+    match Host::parse(&domain) {
+        Ok(Host::Domain(ascii)) => Some(ascii),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registrable_domain() {
+        let cases = [
+            ("www.example.com", Some("example.com")),
+            ("example.co.uk", Some("example.co.uk")),
+            ("blog.example.co.jp", Some("example.co.jp")),
+            ("foo.github.io", Some("foo.github.io")),
+            ("example.xyz", Some("example.xyz")),
+            ("co.uk", None),
+            ("github.io", None),
+            // ワイルドカードと例外（*.ck / !www.ck）
+            ("a.b.ck", Some("a.b.ck")),
+            ("www.ck", Some("www.ck")),
+            // 国際化ドメイン（公司.cn）
+            ("example.xn--55qx5d.cn", Some("example.xn--55qx5d.cn")),
+        ];
+        for (host, expected) in cases {
+            assert_eq!(registrable_domain(host).as_deref(), expected, "{host}");
+        }
+    }
+
+    #[test]
+    fn test_site_identity() {
+        let cases = [
+            ("www.sample.com", "sample.com", "sample"),
+            ("M.YouTube.com", "youtube.com", "youtube"),
+            ("foo.github.io", "foo.github.io", "foo"),
+            ("example.co.uk", "example.co.uk", "example"),
+            ("localhost", "localhost", "localhost"),
+            ("192.168.0.1", "192.168.0.1", "192.168.0.1"),
+            ("[::1]", "::1", "::1"),
+        ];
+        for (host, name, label) in cases {
+            let identity = site_identity(host);
+            assert_eq!(
+                (identity.name.as_str(), identity.label.as_str()),
+                (name, label)
+            );
+        }
+    }
+}
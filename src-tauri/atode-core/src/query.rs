@@ -0,0 +1,645 @@
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::dates::{self, Span};
+use crate::tags;
+
+// 検索式で使えるフィールド
+const FIELDS: &[&str] = &[
+    "tag", "site", "title", "url", "notes", "status", "saved", "updated",
+];
+
+/// 検索式の解析エラー（`position`は検索式の先頭を1とした文字位置）
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "検索式のエラー ({}文字目): {}",
+            self.position, self.message
+        )
+    }
+}
+
+/// 日付フィールドの比較演算子
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+/// 検索式の構文木
+/// 例: `tag:rust (tag:async OR tag:tokio) -site:qiita title:"error handling" saved:>2026-01-01`
+#[derive(Debug, PartialEq, Eq)]
+pub enum Expr {
+    And(Vec<Self>),
+    Or(Vec<Self>),
+    Not(Box<Self>),
+    /// `field:value`（フィールド省略時はタイトル・URL・メモの部分一致）
+    Term {
+        field: Option<String>,
+        comparison: Comparison,
+        value: String,
+        position: usize,
+    },
+}
+
+/// 検索式を解析する。空の検索式は`None`
+pub fn parse(input: &str) -> Result<Option<Expr>, QueryError> {
+    let tokens = tokenize(input)?;
+    if tokens.is_empty() {
+        return Ok(None);
+    }
+
+    let mut parser = Parser {
+        tokens: &tokens,
+        pos: 0,
+        end: input.chars().count() + 1,
+    };
+    let expr = parser.or_expr()?;
+    if let Some(token) = parser.peek() {
+        return Err(error(token.position, "対応する開き括弧がありません"));
+    }
+    Ok(Some(expr))
+}
+
+impl Expr {
+    /// SQLの条件式へ変換する（値はすべて`params`へ積む）
+    /// 記事は`a`、サイトは`s`、ステータスは`st`の別名で結合されている前提
+    pub fn to_sql(
+        &self,
+        db: &Connection,
+        include_descendants: bool,
+        params: &mut Vec<String>,
+    ) -> Result<String, String> {
+        match self {
+            Self::And(exprs) | Self::Or(exprs) => {
+                let joiner = if matches!(self, Self::And(_)) {
+                    " AND "
+                } else {
+                    " OR "
+                };
+                let parts = exprs
+                    .iter()
+                    .map(|expr| expr.to_sql(db, include_descendants, params))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(format!("({})", parts.join(joiner)))
+            }
+            Self::Not(expr) => Ok(format!(
+                "NOT {}",
+                expr.to_sql(db, include_descendants, params)?
+            )),
+            Self::Term {
+                field,
+                comparison,
+                value,
+                position,
+            } => term_sql(
+                db,
+                field.as_deref(),
+                *comparison,
+                value,
+                *position,
+                include_descendants,
+                params,
+            ),
+        }
+    }
+}
+
+fn term_sql(
+    db: &Connection,
+    field: Option<&str>,
+    comparison: Comparison,
+    value: &str,
+    position: usize,
+    include_descendants: bool,
+    params: &mut Vec<String>,
+) -> Result<String, String> {
+    let like = || format!("%{}%", escape_like(value));
+
+    let sql = match field {
+        None => {
+            params.extend([like(), like(), like()]);
+            "(a.title LIKE ? ESCAPE '\\' OR a.url LIKE ? ESCAPE '\\' OR a.notes LIKE ? ESCAPE '\\')"
+                .to_string()
+        }
+        Some("tag") => {
+            params.push(tags::canonical_name(db, value)?);
+            let tag_ids = if include_descendants {
+                tags::SUBTREE_SQL
+            } else {
+                "SELECT id FROM tags WHERE name = ?"
+            };
+            format!(
+                "a.id IN (SELECT at.article_id FROM article_tags at WHERE at.tag_id IN ({tag_ids}))"
+            )
+        }
+        Some("site") => {
+            params.push(like());
+            "COALESCE(s.name, '') LIKE ? ESCAPE '\\'".to_string()
+        }
+        Some(column @ ("title" | "url" | "notes")) => {
+            params.push(like());
+            format!("a.{column} LIKE ? ESCAPE '\\'")
+        }
+        Some("status") => {
+            params.push(value.to_string());
+            "COALESCE(st.name, '') = ? COLLATE NOCASE".to_string()
+        }
+        Some(field @ ("saved" | "updated")) => {
+            let column = if field == "saved" {
+                "a.created_at"
+            } else {
+                "a.updated_at"
+            };
+            return date_sql(column, comparison, value, position, params)
+                .map_err(|e| e.to_string());
+        }
+        Some(field) => {
+            return Err(error(position, &format!("不明なフィールド「{field}」")).to_string())
+        }
+    };
+    Ok(sql)
+}
+
+// 日付の比較
+fn date_sql(
+    column: &str,
+    comparison: Comparison,
+    value: &str,
+    position: usize,
+    params: &mut Vec<String>,
+) -> Result<String, QueryError> {
+    let sql = match date_range(comparison, value, position)? {
+        (Some(start), Some(end)) => {
+            params.extend([dates::to_db_timestamp(start), dates::to_db_timestamp(end)]);
+            format!("({column} >= ? AND {column} < ?)")
+        }
+        (Some(start), None) => {
+            params.push(dates::to_db_timestamp(start));
+            format!("{column} >= ?")
+        }
+        (None, Some(end)) => {
+            params.push(dates::to_db_timestamp(end));
+            format!("{column} < ?")
+        }
+        (None, None) => unreachable!("date_range は少なくとも一方の境界を返す"),
+    };
+    Ok(sql)
+}
+
+/// 日付の範囲（開始以上・終了未満。`None`は制限なし）
+pub type DateRange = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+/// 日付の比較を範囲にする
+///
+/// 日付（YYYY-MM-DD）・相対指定（today, yesterday, "3 days ago" など）はローカルタイムの期間として、
+/// RFC 3339・`now`はその時刻を境に比較する（時刻との`=`は指定できない）
+pub fn date_range(
+    comparison: Comparison,
+    value: &str,
+    position: usize,
+) -> Result<DateRange, QueryError> {
+    let span = dates::resolve_span(value).map_err(|e| error(position, &e))?;
+    Ok(match (span, comparison) {
+        (Span::Instant(_), Comparison::Eq) => {
+            return Err(error(position, "時刻には >, >=, <, <= を指定してください"))
+        }
+        (Span::Instant(instant), Comparison::Gt | Comparison::Ge) => (Some(instant), None),
+        (Span::Instant(instant), Comparison::Lt | Comparison::Le) => (None, Some(instant)),
+        (Span::Period(start, end), Comparison::Eq) => (Some(start), Some(end)),
+        (Span::Period(_, end), Comparison::Gt) => (Some(end), None),
+        (Span::Period(start, _), Comparison::Ge) => (Some(start), None),
+        (Span::Period(start, _), Comparison::Lt) => (None, Some(start)),
+        (Span::Period(_, end), Comparison::Le) => (None, Some(end)),
+    })
+}
+
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+fn error(position: usize, message: &str) -> QueryError {
+    QueryError {
+        position,
+        message: message.to_string(),
+    }
+}
+
+//------------------------------------------------------------------------------------------------
+// 字句解析
+//------------------------------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, Eq)]
+enum TokenKind {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Term {
+        field: Option<String>,
+        comparison: Comparison,
+        value: String,
+    },
+}
+
+#[derive(Debug)]
+struct Token {
+    kind: TokenKind,
+    position: usize,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, QueryError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let position = i + 1;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let kind = match c {
+            '(' => {
+                i += 1;
+                TokenKind::LParen
+            }
+            ')' => {
+                i += 1;
+                TokenKind::RParen
+            }
+            // 語の先頭の`-`は否定（`-site:qiita`）
+            '-' if chars.get(i + 1).is_some_and(|c| !c.is_whitespace()) => {
+                i += 1;
+                TokenKind::Not
+            }
+            _ => {
+                let (kind, next) = term(&chars, i)?;
+                i = next;
+                kind
+            }
+        };
+        tokens.push(Token { kind, position });
+    }
+
+    Ok(tokens)
+}
+
+// 1つの語（`field:op value`・引用符付きの語・AND/OR/NOT）を読む
+fn term(chars: &[char], start: usize) -> Result<(TokenKind, usize), QueryError> {
+    let mut i = start;
+
+    // フィールド名（FIELDS 以外の`xxx:`は、URLなども含めて語の一部として扱う）
+    let mut field = None;
+    let name_end = chars[i..]
+        .iter()
+        .position(|c| !c.is_ascii_alphabetic())
+        .map_or(chars.len(), |n| i + n);
+    if name_end > i && chars.get(name_end) == Some(&':') {
+        let name: String = chars[i..name_end].iter().collect::<String>().to_lowercase();
+        if FIELDS.contains(&name.as_str()) {
+            field = Some(name);
+            i = name_end + 1;
+        }
+    }
+
+    // 比較演算子（日付フィールドのみ）
+    let mut comparison = Comparison::Eq;
+    if field.is_some() {
+        for (op, cmp) in [
+            (">=", Comparison::Ge),
+            ("<=", Comparison::Le),
+            (">", Comparison::Gt),
+            ("<", Comparison::Lt),
+            ("=", Comparison::Eq),
+        ] {
+            let op: Vec<char> = op.chars().collect();
+            if chars[i..].starts_with(&op) {
+                comparison = cmp;
+                i += op.len();
+                break;
+            }
+        }
+        if comparison != Comparison::Eq && !matches!(field.as_deref(), Some("saved" | "updated")) {
+            return Err(error(
+                start + 1,
+                "比較演算子は saved / updated でのみ使えます",
+            ));
+        }
+    }
+
+    // 値（引用符で囲むと空白や括弧も含められる）
+    let value_start = i;
+    let value = if chars.get(i) == Some(&'"') {
+        i += 1;
+        let mut value = String::new();
+        loop {
+            match chars.get(i) {
+                None => return Err(error(value_start + 1, "引用符が閉じられていません")),
+                Some('"') => {
+                    i += 1;
+                    break;
+                }
+                Some('\\') if chars.get(i + 1).is_some() => {
+                    value.push(chars[i + 1]);
+                    i += 2;
+                }
+                Some(&c) => {
+                    value.push(c);
+                    i += 1;
+                }
+            }
+        }
+        value
+    } else {
+        let end = chars[i..]
+            .iter()
+            .position(|&c| c.is_whitespace() || c == '(' || c == ')' || c == '"')
+            .map_or(chars.len(), |n| i + n);
+        let value: String = chars[i..end].iter().collect();
+        i = end;
+
+        // 引用符なしの AND / OR / NOT は演算子
+        if field.is_none() {
+            match value.as_str() {
+                "AND" => return Ok((TokenKind::And, i)),
+                "OR" => return Ok((TokenKind::Or, i)),
+                "NOT" => return Ok((TokenKind::Not, i)),
+                _ => {}
+            }
+        }
+        value
+    };
+
+    if value.trim().is_empty() {
+        return Err(error(value_start + 1, "検索する値がありません"));
+    }
+
+    Ok((
+        TokenKind::Term {
+            field,
+            comparison,
+            value,
+        },
+        i,
+    ))
+}
+
+//------------------------------------------------------------------------------------------------
+// 構文解析（優先順位: NOT > AND（省略可） > OR）
+//------------------------------------------------------------------------------------------------
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    // 検索式の末尾の位置（エラー表示用）
+    end: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next_position(&self) -> usize {
+        self.peek().map_or(self.end, |token| token.position)
+    }
+
+    fn or_expr(&mut self) -> Result<Expr, QueryError> {
+        let mut exprs = vec![self.and_expr()?];
+        while matches!(
+            self.peek(),
+            Some(Token {
+                kind: TokenKind::Or,
+                ..
+            })
+        ) {
+            self.pos += 1;
+            exprs.push(self.and_expr()?);
+        }
+        Ok(if exprs.len() == 1 {
+            exprs.remove(0)
+        } else {
+            Expr::Or(exprs)
+        })
+    }
+
+    fn and_expr(&mut self) -> Result<Expr, QueryError> {
+        let mut exprs = vec![self.unary()?];
+        loop {
+            match self.peek().map(|token| &token.kind) {
+                Some(TokenKind::And) => {
+                    self.pos += 1;
+                    exprs.push(self.unary()?);
+                }
+                // 演算子なしで並べた条件はAND
+                Some(TokenKind::LParen | TokenKind::Not | TokenKind::Term { .. }) => {
+                    exprs.push(self.unary()?);
+                }
+                _ => break,
+            }
+        }
+        Ok(if exprs.len() == 1 {
+            exprs.remove(0)
+        } else {
+            Expr::And(exprs)
+        })
+    }
+
+    fn unary(&mut self) -> Result<Expr, QueryError> {
+        if matches!(
+            self.peek(),
+            Some(Token {
+                kind: TokenKind::Not,
+                ..
+            })
+        ) {
+            self.pos += 1;
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, QueryError> {
+        let position = self.next_position();
+        let Some(token) = self.tokens.get(self.pos) else {
+            return Err(error(position, "条件がありません"));
+        };
+        self.pos += 1;
+
+        match &token.kind {
+            TokenKind::LParen => {
+                if matches!(
+                    self.peek(),
+                    Some(Token {
+                        kind: TokenKind::RParen,
+                        ..
+                    })
+                ) {
+                    return Err(error(position, "括弧の中が空です"));
+                }
+                let expr = self.or_expr()?;
+                match self.peek() {
+                    Some(Token {
+                        kind: TokenKind::RParen,
+                        ..
+                    }) => {
+                        self.pos += 1;
+                        Ok(expr)
+                    }
+                    _ => Err(error(position, "閉じ括弧がありません")),
+                }
+            }
+            TokenKind::Term {
+                field,
+                comparison,
+                value,
+            } => Ok(Expr::Term {
+                field: field.clone(),
+                comparison: *comparison,
+                value: value.clone(),
+                position,
+            }),
+            TokenKind::And | TokenKind::Or => Err(error(position, "演算子の前に条件がありません")),
+            TokenKind::RParen => Err(error(position, "対応する開き括弧がありません")),
+            TokenKind::Not => unreachable!("NOTはunary()で処理済み"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(field: &str, value: &str, position: usize) -> Expr {
+        Expr::Term {
+            field: Some(field.to_string()),
+            comparison: Comparison::Eq,
+            value: value.to_string(),
+            position,
+        }
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let expr = parse(r#"tag:rust (tag:async OR tag:tokio) -site:qiita title:"error handling""#)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            expr,
+            Expr::And(vec![
+                term("tag", "rust", 1),
+                Expr::Or(vec![term("tag", "async", 11), term("tag", "tokio", 24)]),
+                Expr::Not(Box::new(term("site", "qiita", 36))),
+                term("title", "error handling", 47),
+            ])
+        );
+
+        let expr = parse("saved:>=2026-01-01 NOT 図解").unwrap().unwrap();
+        assert_eq!(
+            expr,
+            Expr::And(vec![
+                Expr::Term {
+                    field: Some("saved".into()),
+                    comparison: Comparison::Ge,
+                    value: "2026-01-01".into(),
+                    position: 1,
+                },
+                Expr::Not(Box::new(Expr::Term {
+                    field: None,
+                    comparison: Comparison::Eq,
+                    value: "図解".into(),
+                    position: 24,
+                })),
+            ])
+        );
+
+        // フィールド名でない`xxx:`はそのまま語として検索する
+        let bare = |value: &str, position| Expr::Term {
+            field: None,
+            comparison: Comparison::Eq,
+            value: value.into(),
+            position,
+        };
+        assert_eq!(
+            parse("https://qiita.com/foo").unwrap().unwrap(),
+            bare("https://qiita.com/foo", 1)
+        );
+        assert_eq!(
+            parse("memo: tga:rust").unwrap().unwrap(),
+            Expr::And(vec![bare("memo:", 1), bare("tga:rust", 7)])
+        );
+
+        assert_eq!(parse("  ").unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_errors_report_position() {
+        let position = |input: &str| parse(input).unwrap_err().position;
+
+        assert_eq!(position("tag:rust (tag:async"), 10);
+        assert_eq!(position("tag:rust )"), 10);
+        assert_eq!(position("tag:rust OR"), 12);
+        assert_eq!(position("OR tag:rust"), 1);
+        assert_eq!(position("tag:rust ()"), 10);
+        assert_eq!(position(r#"title:"error"#), 7);
+        assert_eq!(position("site:"), 6);
+        assert_eq!(position("tag:>rust"), 1);
+    }
+
+    #[test]
+    fn test_date_conditions() {
+        let mut params = Vec::new();
+        let sql = date_sql("a.created_at", Comparison::Gt, "2026-01-31", 1, &mut params).unwrap();
+        assert_eq!(sql, "a.created_at >= ?");
+        let feb_1 = chrono::NaiveDate::from_ymd_opt(2026, 2, 1).unwrap();
+        assert_eq!(
+            params,
+            [dates::to_db_timestamp(
+                dates::local_start_of_day(feb_1).unwrap()
+            )]
+        );
+
+        let err = date_sql("a.created_at", Comparison::Eq, "2026/01/01", 7, &mut params);
+        assert_eq!(err.unwrap_err().position, 7);
+
+        // 相対指定も1日単位（昨日の0時〜今日の0時）で比較する
+        let today = chrono::Local::now().date_naive();
+        let yesterday = today.pred_opt().unwrap();
+        let midnight = |day| dates::to_db_timestamp(dates::local_start_of_day(day).unwrap());
+        let mut params = Vec::new();
+        let sql = date_sql("a.created_at", Comparison::Eq, "yesterday", 1, &mut params).unwrap();
+        assert_eq!(sql, "(a.created_at >= ? AND a.created_at < ?)");
+        assert_eq!(params, [midnight(yesterday), midnight(today)]);
+
+        let mut params = Vec::new();
+        let sql = date_sql("a.created_at", Comparison::Le, "yesterday", 1, &mut params).unwrap();
+        assert_eq!(sql, "a.created_at < ?");
+        assert_eq!(params, [midnight(today)]);
+
+        let mut params = Vec::new();
+        date_sql("a.created_at", Comparison::Gt, "yesterday", 1, &mut params).unwrap();
+        assert_eq!(params, [midnight(today)]);
+
+        let err = date_sql("a.created_at", Comparison::Eq, "now", 5, &mut params);
+        assert_eq!(err.unwrap_err().position, 5);
+    }
+}
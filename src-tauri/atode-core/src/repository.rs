@@ -0,0 +1,171 @@
+use rusqlite::Connection;
+
+/// 書き込み操作を1トランザクションで実行する（エラーなら途中までの変更もすべて巻き戻す）
+///
+/// 記事・タグ・サイト・ステータスなどを変更するコマンドはすべてこれを通す。
+/// 各モジュールの書き込み関数は`&Connection`を受け取り、自分ではトランザクションを開始しない
+/// （入れ子にできないため。まとめて1操作にしたいときは同じ`operation`の中で呼ぶ）
+pub fn write<T>(
+    db: &mut Connection,
+    operation: impl FnOnce(&Connection) -> Result<T, String>,
+) -> Result<T, String> {
+    let tx = db.transaction().map_err(|e| e.to_string())?;
+    match operation(&tx) {
+        Ok(value) => {
+            tx.commit().map_err(|e| e.to_string())?;
+            Ok(value)
+        }
+        Err(e) => {
+            tx.rollback()
+                .map_err(|rollback| format!("{e}（ロールバック失敗: {rollback}）"))?;
+            log_error!("↩️ 変更を取り消しました: {e}");
+            Err(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::articles::{edit_article, store_article, SaveArticleRequest};
+    use crate::resave::ResavePolicy;
+    use crate::url_rules::UrlRules;
+    use crate::{canonical, init_database, tags};
+
+    // 障害の注入: `table`への`action`（INSERT / UPDATE / DELETE）を必ず失敗させる
+    fn inject_fault(db: &Connection, table: &str, action: &str) {
+        db.execute_batch(&format!(
+            "CREATE TEMP TRIGGER inject_fault BEFORE {action} ON {table}
+             BEGIN SELECT RAISE(ABORT, 'injected fault'); END;"
+        ))
+        .unwrap();
+    }
+
+    fn clear_fault(db: &Connection) {
+        db.execute_batch("DROP TRIGGER inject_fault").unwrap();
+    }
+
+    fn count(db: &Connection, table: &str) -> i64 {
+        db.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+            row.get(0)
+        })
+        .unwrap()
+    }
+
+    fn save(db: &mut Connection, url: &str, tags: &str) -> Result<(), String> {
+        let request = SaveArticleRequest {
+            url: url.to_string(),
+            title: "title".to_string(),
+            tags: Some(tags.to_string()),
+        };
+        write(db, |db| {
+            store_article(db, &UrlRules::default(), &ResavePolicy::default(), request)
+        })
+        .map(|_| ())
+    }
+
+    #[test]
+    fn test_error_rolls_back_earlier_statements() {
+        let mut db = init_database(":memory:").unwrap();
+        let result: Result<(), String> = write(&mut db, |db| {
+            db.execute("INSERT INTO tags (name) VALUES ('rust')", [])
+                .map_err(|e| e.to_string())?;
+            Err("injected fault".to_string())
+        });
+        assert_eq!(result.unwrap_err(), "injected fault");
+        assert_eq!(count(&db, "tags"), 0);
+    }
+
+    #[test]
+    fn test_failed_save_leaves_nothing_behind() {
+        let mut db = init_database(":memory:").unwrap();
+
+        // タグ付けで失敗しても、記事・サイト・タグだけが残ることはない
+        inject_fault(&db, "article_tags", "INSERT");
+        let error = save(&mut db, "https://example.com/a", "rust").unwrap_err();
+        assert!(error.contains("injected fault"));
+        for table in ["articles", "sites", "tags", "article_tags"] {
+            assert_eq!(count(&db, table), 0, "{table}");
+        }
+
+        clear_fault(&db);
+        save(&mut db, "https://example.com/a", "rust").unwrap();
+        assert_eq!(count(&db, "article_tags"), 1);
+    }
+
+    #[test]
+    fn test_failed_edit_keeps_tags_and_site() {
+        let mut db = init_database(":memory:").unwrap();
+        save(&mut db, "https://example.com/a", "rust").unwrap();
+
+        // タグの置き換え後、記事の更新で失敗する
+        inject_fault(&db, "articles", "UPDATE OF url");
+        let request = SaveArticleRequest {
+            url: "https://zenn.dev/a".to_string(),
+            title: "edited".to_string(),
+            tags: Some("go".to_string()),
+        };
+        let result = write(&mut db, |db| {
+            edit_article(db, &UrlRules::default(), 1, &request, false)
+        });
+        assert!(result.is_err());
+
+        let (url, site, tag): (String, String, String) = db
+            .query_row(
+                "SELECT a.url, s.name, t.name
+                FROM articles a
+                JOIN sites s
+                  ON a.site_id = s.id
+                JOIN article_tags at
+                  ON at.article_id = a.id
+                JOIN tags t
+                  ON at.tag_id = t.id",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(
+            (url.as_str(), site.as_str(), tag.as_str()),
+            ("https://example.com/a", "example.com", "rust")
+        );
+        assert_eq!(count(&db, "sites"), 1);
+    }
+
+    #[test]
+    fn test_failed_merges_change_nothing() {
+        let mut db = init_database(":memory:").unwrap();
+        db.execute_batch(
+            "INSERT INTO tags (name) VALUES ('rust'), ('rustlang');
+             INSERT INTO articles (url, title) VALUES ('https://example.com/a', 'A');
+             INSERT INTO articles (url, title) VALUES ('http://example.com/a/', 'B');
+             INSERT INTO article_tags (article_id, tag_id) VALUES (1, 1), (2, 2);",
+        )
+        .unwrap();
+
+        // 記事の統合: 統合元の削除で失敗する
+        inject_fault(&db, "articles", "DELETE");
+        assert!(write(&mut db, |db| canonical::merge_duplicates(db, None)).is_err());
+        assert_eq!(count(&db, "articles"), 2);
+        assert_eq!(count(&db, "article_tags"), 2);
+        assert_eq!(count(&db, "article_status_history"), 2);
+        clear_fault(&db);
+
+        // タグの統合: 別名の登録で失敗する
+        inject_fault(&db, "tag_aliases", "INSERT");
+        assert!(write(&mut db, |db| tags::merge_tags(
+            db,
+            &["rustlang".into()],
+            "rust"
+        ))
+        .is_err());
+        assert_eq!(count(&db, "tags"), 2);
+        let tag_ids: Vec<i64> = db
+            .prepare("SELECT tag_id FROM article_tags ORDER BY article_id")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(tag_ids, [1, 2]);
+    }
+}
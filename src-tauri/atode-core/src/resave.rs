@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 保存済みの記事を再保存したときの扱い（config.json の `resave`）
+///
+/// 既定ではタグは追加のみ、タイトルは保存済みのまま（手で整えた内容を消さない）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResavePolicy {
+    pub tags: TagPolicy,
+    pub title: TitlePolicy,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagPolicy {
+    /// 新しいタグを追加し、既存のタグは残す
+    #[default]
+    Merge,
+    /// 保存時のタグに置き換える
+    Replace,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TitlePolicy {
+    /// 保存済みのタイトルを残す
+    #[default]
+    Keep,
+    /// 保存時のタイトルに置き換える
+    Replace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SaveStatus {
+    Created,
+    /// 保存済みの記事のタグ・タイトルなどが変わった
+    Updated,
+    /// 保存済みの記事で、更新日時以外は変わっていない
+    Unchanged,
+}
+
+/// 記事の保存結果（何が変わったか）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveResult {
+    pub status: SaveStatus,
+    pub article_id: i64,
+    pub added_tags: Vec<String>,
+    pub removed_tags: Vec<String>,
+    pub title_changed: bool,
+    /// ゴミ箱から戻した
+    pub restored: bool,
+    /// 画面・ログ用の説明（例: 「保存済みの記事です（タグを追加: rust）」）
+    pub message: String,
+}
+
+impl SaveResult {
+    pub fn created(article_id: i64, added_tags: Vec<String>) -> Self {
+        Self::new(
+            SaveStatus::Created,
+            article_id,
+            added_tags,
+            Vec::new(),
+            false,
+            false,
+        )
+    }
+
+    /// 再保存の結果（変更が無ければ`Unchanged`）
+    pub fn resaved(
+        article_id: i64,
+        added_tags: Vec<String>,
+        removed_tags: Vec<String>,
+        title_changed: bool,
+        restored: bool,
+    ) -> Self {
+        let changed =
+            !added_tags.is_empty() || !removed_tags.is_empty() || title_changed || restored;
+        let status = if changed {
+            SaveStatus::Updated
+        } else {
+            SaveStatus::Unchanged
+        };
+        Self::new(
+            status,
+            article_id,
+            added_tags,
+            removed_tags,
+            title_changed,
+            restored,
+        )
+    }
+
+    fn new(
+        status: SaveStatus,
+        article_id: i64,
+        added_tags: Vec<String>,
+        removed_tags: Vec<String>,
+        title_changed: bool,
+        restored: bool,
+    ) -> Self {
+        let mut changes = Vec::new();
+        if restored {
+            changes.push("ゴミ箱から戻しました".to_string());
+        }
+        if title_changed {
+            changes.push("タイトルを更新".to_string());
+        }
+        if !added_tags.is_empty() {
+            changes.push(format!("タグを追加: {}", added_tags.join(", ")));
+        }
+        if !removed_tags.is_empty() {
+            changes.push(format!("タグを削除: {}", removed_tags.join(", ")));
+        }
+
+        let message = match status {
+            SaveStatus::Created if added_tags.is_empty() => "記事を追加しました".to_string(),
+            SaveStatus::Created => format!("記事を追加しました（タグ: {}）", added_tags.join(", ")),
+            SaveStatus::Updated => format!("保存済みの記事です（{}）", changes.join("、")),
+            SaveStatus::Unchanged => "保存済みの記事です（変更なし）".to_string(),
+        };
+
+        Self {
+            status,
+            article_id,
+            added_tags,
+            removed_tags,
+            title_changed,
+            restored,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for SaveResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
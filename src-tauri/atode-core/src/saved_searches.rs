@@ -0,0 +1,208 @@
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::articles::{count_articles, SearchFilters};
+
+/// 保存した検索（`count`は現在の一致件数）
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub id: i64,
+    pub name: String,
+    pub filters: SearchFilters,
+    pub count: u32,
+}
+
+/// 保存した検索の一覧（名前順。件数はその都度数える）
+pub fn list_saved_searches(db: &Connection) -> Result<Vec<SavedSearch>, String> {
+    let mut stmt = db
+        .prepare("SELECT id, name, filters FROM saved_searches ORDER BY name COLLATE NOCASE, id")
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    rows.into_iter()
+        .map(|(id, name, filters)| with_count(db, id, name, &filters))
+        .collect()
+}
+
+/// 保存した検索を1件取得
+pub fn get_saved_search(db: &Connection, id: i64) -> Result<SavedSearch, String> {
+    let (name, filters): (String, String) = db
+        .query_row(
+            "SELECT name, filters FROM saved_searches WHERE id = ?",
+            [id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("保存した検索が見つかりません (ID: {id})"))?;
+
+    with_count(db, id, name, &filters)
+}
+
+/// 検索条件に名前を付けて保存し、IDを返す
+pub fn create_saved_search(
+    db: &Connection,
+    name: &str,
+    filters: SearchFilters,
+) -> Result<i64, String> {
+    let name = validate_name(db, name, None)?;
+    let filters = filters_json(db, filters)?;
+
+    db.execute(
+        "INSERT INTO saved_searches (name, filters) VALUES (?, ?)",
+        params![name, filters],
+    )
+    .map_err(|e| e.to_string())?;
+
+    log!("🔖 検索を保存: {name}");
+    Ok(db.last_insert_rowid())
+}
+
+/// 保存した検索の名前と検索条件を更新
+pub fn update_saved_search(
+    db: &Connection,
+    id: i64,
+    name: &str,
+    filters: SearchFilters,
+) -> Result<(), String> {
+    let name = validate_name(db, name, Some(id))?;
+    let filters = filters_json(db, filters)?;
+
+    let rows = db
+        .execute(
+            "UPDATE saved_searches
+             SET name = ?, filters = ?, updated_at = CURRENT_TIMESTAMP
+             WHERE id = ?",
+            params![name, filters, id],
+        )
+        .map_err(|e| e.to_string())?;
+    if rows == 0 {
+        return Err(format!("保存した検索が見つかりません (ID: {id})"));
+    }
+
+    log!("🔖 保存した検索を更新: {name}");
+    Ok(())
+}
+
+/// 保存した検索を削除
+pub fn delete_saved_search(db: &Connection, id: i64) -> Result<(), String> {
+    let rows = db
+        .execute("DELETE FROM saved_searches WHERE id = ?", [id])
+        .map_err(|e| e.to_string())?;
+    if rows == 0 {
+        return Err(format!("保存した検索が見つかりません (ID: {id})"));
+    }
+
+    log!("🔖 保存した検索を削除: ID {id}");
+    Ok(())
+}
+
+fn with_count(
+    db: &Connection,
+    id: i64,
+    name: String,
+    filters: &str,
+) -> Result<SavedSearch, String> {
+    let filters: SearchFilters = serde_json::from_str(filters).map_err(|e| e.to_string())?;
+    let count = count_articles(db, &filters)?;
+    Ok(SavedSearch {
+        id,
+        name,
+        filters,
+        count,
+    })
+}
+
+fn validate_name(db: &Connection, name: &str, id: Option<i64>) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("名前が空です".to_string());
+    }
+
+    let existing: Option<i64> = db
+        .query_row(
+            "SELECT id FROM saved_searches WHERE name = ?",
+            [name],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if existing.is_some() && existing != id {
+        return Err(format!("「{name}」という名前の検索は既にあります"));
+    }
+    Ok(name.to_string())
+}
+
+// ページングは保存しない。検索式の誤りは保存前に検出する
+fn filters_json(db: &Connection, filters: SearchFilters) -> Result<String, String> {
+    let filters = SearchFilters {
+        limit: None,
+        offset: None,
+        ..filters
+    };
+    count_articles(db, &filters)?;
+    serde_json::to_string(&filters).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init_database;
+
+    #[test]
+    fn test_saved_search_lifecycle() {
+        let db = init_database(":memory:").unwrap();
+        db.execute_batch(
+            "INSERT INTO sites (name) VALUES ('zenn.dev'), ('qiita.com');
+             INSERT INTO articles (url, title, site_id) VALUES ('https://zenn.dev/a', 'A', 1);
+             INSERT INTO articles (url, title, site_id) VALUES ('https://zenn.dev/b', 'B', 1);
+             INSERT INTO articles (url, title, site_id) VALUES ('https://qiita.com/c', 'C', 2);",
+        )
+        .unwrap();
+
+        let filters = |site: &str| SearchFilters {
+            site: Some(site.to_string()),
+            limit: Some(50),
+            ..Default::default()
+        };
+        let id = create_saved_search(&db, " Zenn ", filters("zenn")).unwrap();
+        assert!(create_saved_search(&db, "Zenn", filters("zenn")).is_err());
+
+        let saved = get_saved_search(&db, id).unwrap();
+        assert_eq!((saved.name.as_str(), saved.count), ("Zenn", 2));
+        assert_eq!(saved.filters.limit, None);
+
+        // 件数は保存時点ではなく現在の記事数
+        db.execute(
+            "INSERT INTO articles (url, title, site_id) VALUES ('https://zenn.dev/d', 'D', 1)",
+            [],
+        )
+        .unwrap();
+        assert_eq!(list_saved_searches(&db).unwrap()[0].count, 3);
+
+        update_saved_search(&db, id, "Qiita", filters("qiita")).unwrap();
+        assert_eq!(get_saved_search(&db, id).unwrap().count, 1);
+
+        // 解析できない検索式は保存しない
+        let invalid = SearchFilters {
+            query: Some("tag:rust (".to_string()),
+            ..Default::default()
+        };
+        assert!(create_saved_search(&db, "broken", invalid).is_err());
+
+        delete_saved_search(&db, id).unwrap();
+        assert!(list_saved_searches(&db).unwrap().is_empty());
+        assert!(delete_saved_search(&db, id).is_err());
+    }
+}
@@ -0,0 +1,236 @@
+/// スニペット内で一致箇所を囲むマーカー（フロントエンド側でHTMLエスケープ後に`<mark>`へ置換する）
+pub const HIGHLIGHT_START: char = '\u{2}';
+pub const HIGHLIGHT_END: char = '\u{3}';
+
+// trigramトークナイザで索引を引ける最小の文字数
+const TRIGRAM_MIN_CHARS: usize = 3;
+
+// メモのスニペットで一致箇所の前後に残す文字数
+const EXCERPT_BEFORE: usize = 20;
+const EXCERPT_AFTER: usize = 60;
+
+/// キーワード検索の条件（タイトル・URL・メモが対象）
+/// 全文検索インデックスは文字trigramで作られているため、日本語のように空白で区切られない
+/// 文章でも部分一致で引ける。ただし3文字未満の語（「図解」など）は索引で引けないのでLIKEで照合する
+#[derive(Debug, PartialEq, Eq)]
+pub struct KeywordQuery {
+    /// 入力された検索語（ハイライト用）
+    pub terms: Vec<String>,
+    /// 3文字以上の語から作ったFTS5のMATCH式
+    pub match_expr: Option<String>,
+    /// 3文字未満の語のLIKEパターン（`\`でエスケープ済み）
+    pub like_patterns: Vec<String>,
+}
+
+/// キーワード入力を検索条件に変換
+/// 空白区切りの各語をフレーズとして扱い（演算子として解釈させない）、すべての語をAND結合する
+pub fn parse_keywords(text: &str) -> Option<KeywordQuery> {
+    let terms: Vec<String> = text.split_whitespace().map(str::to_string).collect();
+    if terms.is_empty() {
+        return None;
+    }
+
+    let (long, short): (Vec<&String>, Vec<&String>) = terms
+        .iter()
+        .partition(|term| term.chars().count() >= TRIGRAM_MIN_CHARS);
+
+    let match_expr = if long.is_empty() {
+        None
+    } else {
+        Some(
+            long.iter()
+                .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+                .collect::<Vec<_>>()
+                .join(" "),
+        )
+    };
+
+    let like_patterns = short
+        .iter()
+        .map(|term| {
+            let escaped = term
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            format!("%{escaped}%")
+        })
+        .collect();
+
+    Some(KeywordQuery {
+        terms,
+        match_expr,
+        like_patterns,
+    })
+}
+
+/// キーワードに一致する記事を`m(article_id, rank)`として返すCTE（`WITH`句）を組み立てる
+/// bm25は全文検索のクエリ内でしか使えないため、結合前に実体化しておく
+pub fn match_cte(keywords: &KeywordQuery, params: &mut Vec<String>) -> String {
+    let mut conditions = Vec::new();
+    if let Some(match_expr) = &keywords.match_expr {
+        conditions.push("articles_fts MATCH ?");
+        params.push(match_expr.clone());
+    }
+    for pattern in &keywords.like_patterns {
+        conditions.push(
+            "(title LIKE ? ESCAPE '\\' OR url LIKE ? ESCAPE '\\' OR notes LIKE ? ESCAPE '\\')",
+        );
+        params.extend([pattern.clone(), pattern.clone(), pattern.clone()]);
+    }
+
+    // LIKEだけで照合する場合は関連度を付けられない
+    let rank = if keywords.match_expr.is_some() {
+        "bm25(articles_fts)"
+    } else {
+        "0"
+    };
+
+    format!(
+        "WITH m AS MATERIALIZED (
+            SELECT rowid AS article_id, {rank} AS rank
+            FROM articles_fts
+            WHERE {}
+        ) ",
+        conditions.join(" AND ")
+    )
+}
+
+/// 検索結果に添えるスニペット（タイトル → URL → メモの順に、一致した最初のもの）
+/// メモは長くなりうるので、最初の一致箇所の前後だけを切り出す
+pub fn snippet(title: &str, url: &str, notes: &str, terms: &[String]) -> Option<String> {
+    highlight(title, terms)
+        .or_else(|| highlight(url, terms))
+        .or_else(|| excerpt(notes, terms))
+}
+
+/// 検索語の出現箇所をマーカーで囲む（大文字小文字は区別しない）。一致が無ければ`None`
+pub fn highlight(text: &str, terms: &[String]) -> Option<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let marked = mark_matches(&chars, terms);
+    if !marked.contains(&true) {
+        return None;
+    }
+    Some(render(&chars, &marked))
+}
+
+// 最初の一致箇所の前後を切り出してハイライト
+fn excerpt(text: &str, terms: &[String]) -> Option<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let marked = mark_matches(&chars, terms);
+    let first = marked.iter().position(|&m| m)?;
+
+    let start = first.saturating_sub(EXCERPT_BEFORE);
+    let end = (first + EXCERPT_AFTER).min(chars.len());
+
+    let mut result = String::new();
+    if start > 0 {
+        result.push('…');
+    }
+    result.push_str(&render(&chars[start..end], &marked[start..end]));
+    if end < chars.len() {
+        result.push('…');
+    }
+    Some(result)
+}
+
+// 各文字が検索語の一致範囲に含まれるか
+fn mark_matches(chars: &[char], terms: &[String]) -> Vec<bool> {
+    let folded: Vec<char> = chars.iter().copied().map(fold_case).collect();
+    let mut marked = vec![false; chars.len()];
+
+    for term in terms {
+        let needle: Vec<char> = term.chars().map(fold_case).collect();
+        if needle.is_empty() || needle.len() > folded.len() {
+            continue;
+        }
+        for start in 0..=(folded.len() - needle.len()) {
+            if folded[start..start + needle.len()] == needle[..] {
+                marked[start..start + needle.len()].fill(true);
+            }
+        }
+    }
+
+    marked
+}
+
+// 一致範囲をマーカーで囲んだ文字列を組み立てる（改行は空白に置き換える）
+fn render(chars: &[char], marked: &[bool]) -> String {
+    let mut result = String::with_capacity(chars.len() + 8);
+    let mut in_mark = false;
+    for (&c, &is_marked) in chars.iter().zip(marked) {
+        if is_marked != in_mark {
+            result.push(if is_marked {
+                HIGHLIGHT_START
+            } else {
+                HIGHLIGHT_END
+            });
+            in_mark = is_marked;
+        }
+        result.push(if c == '\n' || c == '\r' { ' ' } else { c });
+    }
+    if in_mark {
+        result.push(HIGHLIGHT_END);
+    }
+    result
+}
+
+// 1文字単位の大文字小文字の畳み込み（文字数が変わる変換は行わない）
+fn fold_case(c: char) -> char {
+    let mut lower = c.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(l), None) => l,
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_keywords() {
+        let query = parse_keywords("所有権  図解 rust").unwrap();
+        assert_eq!(query.terms, ["所有権", "図解", "rust"]);
+        assert_eq!(query.match_expr.as_deref(), Some("\"所有権\" \"rust\""));
+        assert_eq!(query.like_patterns, ["%図解%"]);
+
+        // FTS5の演算子や引用符、LIKEのワイルドカードはそのまま検索語として扱う
+        let query = parse_keywords("\"hi\" OR 5%").unwrap();
+        assert_eq!(query.match_expr.as_deref(), Some("\"\"\"hi\"\"\""));
+        assert_eq!(query.like_patterns, ["%OR%", "%5\\%%"]);
+
+        assert_eq!(parse_keywords("   "), None);
+    }
+
+    #[test]
+    fn test_highlight() {
+        let terms = vec!["rust".to_string(), "図解".to_string()];
+        assert_eq!(
+            highlight("Rustの所有権を図解で理解する", &terms).as_deref(),
+            Some("\u{2}Rust\u{3}の所有権を\u{2}図解\u{3}で理解する")
+        );
+        assert_eq!(highlight("Python tips", &terms), None);
+
+        // URLにしか無い語はURL側でハイライト
+        assert_eq!(
+            snippet("Learn", "https://rust-lang.org/", "", &terms).as_deref(),
+            Some("https://\u{2}rust\u{3}-lang.org/")
+        );
+    }
+
+    #[test]
+    fn test_notes_excerpt() {
+        let terms = vec!["tokio".to_string()];
+        let notes = format!(
+            "{}\n要点: tokio の select! を使う\n{}",
+            "あ".repeat(30),
+            "い".repeat(80)
+        );
+
+        let snippet = snippet("Async Rust", "https://example.com/", &notes, &terms).unwrap();
+        assert!(snippet.starts_with('…'));
+        assert!(snippet.ends_with('…'));
+        assert!(snippet.contains(" 要点: \u{2}tokio\u{3} の select! を使う "));
+        assert_eq!(snippet.chars().count(), EXCERPT_BEFORE + EXCERPT_AFTER + 4);
+    }
+}
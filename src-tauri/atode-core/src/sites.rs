@@ -0,0 +1,269 @@
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+/// サイト（記事数付き）
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SiteSummary {
+    pub id: i64,
+    pub name: String,
+    pub article_count: u32,
+}
+
+/// ホスト名の別名
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SiteAlias {
+    pub host: String,
+    pub site: String,
+}
+
+/// ホスト名の表記を揃える（前後の空白・末尾のドットを除き小文字化）
+pub fn normalize_host(host: &str) -> String {
+    host.trim().trim_end_matches('.').to_lowercase()
+}
+
+/// ホスト名（別名）またはサイト名からサイトIDを探す
+pub fn find_site_id(db: &Connection, host_or_name: &str) -> Result<Option<i64>, String> {
+    db.query_row(
+        "SELECT site_id FROM site_aliases WHERE host = ?1
+         UNION ALL
+         SELECT id FROM sites WHERE name = ?1
+         LIMIT 1",
+        [normalize_host(host_or_name)],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+/// サイト一覧（記事の多い順。ゴミ箱内の記事は数えない）
+pub fn list_sites(db: &Connection) -> Result<Vec<SiteSummary>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT
+               s.id
+             , s.name
+             , COUNT(a.id) as article_count
+            FROM sites s
+            LEFT JOIN articles a
+              ON a.site_id = s.id
+             AND a.deleted_at IS NULL
+            GROUP BY s.id
+            ORDER BY article_count DESC, s.name",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let sites = stmt
+        .query_map([], |row| {
+            Ok(SiteSummary {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                article_count: row.get(2)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    sites
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+/// サイト名を変更
+pub fn rename_site(db: &Connection, name: &str, new_name: &str) -> Result<(), String> {
+    let new_name = normalize_host(new_name);
+    if new_name.is_empty() {
+        return Err("サイト名が空です".to_string());
+    }
+    let site_id = get_site_id(db, name)?;
+    if let Some(other_id) = find_site_id(db, &new_name)? {
+        if other_id != site_id {
+            return Err(format!(
+                "サイト「{new_name}」は既に存在します（統合する場合はマージしてください）"
+            ));
+        }
+    }
+
+    db.execute(
+        "UPDATE sites SET name = ? WHERE id = ?",
+        params![new_name, site_id],
+    )
+    .map_err(|e| e.to_string())?;
+    // 自分の別名を正式名にした場合、その別名は不要になる
+    db.execute("DELETE FROM site_aliases WHERE host = ?", [&new_name])
+        .map_err(|e| e.to_string())?;
+
+    log!("サイト名変更: {} → {new_name}", name.trim());
+    Ok(())
+}
+
+/// `sources`のサイトを`target`へ統合し、統合元のサイトを削除する
+/// 統合元の名前は`target`の別名として残るので、以後そのホストから保存しても`target`に紐づく
+pub fn merge_sites(db: &Connection, sources: &[String], target: &str) -> Result<(), String> {
+    let target_id = get_site_id(db, target)?;
+
+    for source in sources {
+        let source_id = get_site_id(db, source)?;
+        if source_id == target_id {
+            continue;
+        }
+
+        db.execute(
+            "UPDATE articles SET site_id = ? WHERE site_id = ?",
+            params![target_id, source_id],
+        )
+        .map_err(|e| e.to_string())?;
+        db.execute(
+            "UPDATE site_aliases SET site_id = ? WHERE site_id = ?",
+            params![target_id, source_id],
+        )
+        .map_err(|e| e.to_string())?;
+        db.execute(
+            "INSERT OR REPLACE INTO site_aliases (host, site_id)
+             SELECT name, ? FROM sites WHERE id = ?",
+            params![target_id, source_id],
+        )
+        .map_err(|e| e.to_string())?;
+        db.execute("DELETE FROM sites WHERE id = ?", [source_id])
+            .map_err(|e| e.to_string())?;
+
+        log!("サイト統合: {} → {}", source.trim(), target.trim());
+    }
+
+    Ok(())
+}
+
+/// ホスト名の別名一覧
+pub fn list_aliases(db: &Connection) -> Result<Vec<SiteAlias>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT al.host, s.name
+            FROM site_aliases al
+            JOIN sites s
+              ON al.site_id = s.id
+            ORDER BY s.name, al.host",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let aliases = stmt
+        .query_map([], |row| {
+            Ok(SiteAlias {
+                host: row.get(0)?,
+                site: row.get(1)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    aliases
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+/// ホスト名の別名を登録する（既に登録済みなら参照先を付け替える）
+/// 登録済みの記事は変わらないので、既存のサイトをまとめる場合は`merge_sites`を使う
+pub fn add_alias(db: &Connection, host: &str, site: &str) -> Result<(), String> {
+    let host = normalize_host(host);
+    if host.is_empty() {
+        return Err("ホスト名が空です".to_string());
+    }
+    let site_id = get_site_id(db, site)?;
+
+    let existing_site: Option<i64> = db
+        .query_row("SELECT id FROM sites WHERE name = ?", [&host], |row| {
+            row.get(0)
+        })
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if existing_site.is_some() {
+        return Err(format!(
+            "サイト「{host}」が存在するため別名にできません（統合する場合はマージしてください）"
+        ));
+    }
+
+    db.execute(
+        "INSERT INTO site_aliases (host, site_id) VALUES (?, ?)
+         ON CONFLICT(host) DO UPDATE SET site_id = excluded.site_id",
+        params![host, site_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    log!("サイト別名登録: {host} → {}", site.trim());
+    Ok(())
+}
+
+/// ホスト名の別名を削除する
+pub fn remove_alias(db: &Connection, host: &str) -> Result<(), String> {
+    let host = normalize_host(host);
+    let rows = db
+        .execute("DELETE FROM site_aliases WHERE host = ?", [&host])
+        .map_err(|e| e.to_string())?;
+    if rows == 0 {
+        return Err(format!("別名「{host}」が見つかりません"));
+    }
+    Ok(())
+}
+
+fn get_site_id(db: &Connection, name: &str) -> Result<i64, String> {
+    find_site_id(db, name)?.ok_or_else(|| format!("サイト「{}」が見つかりません", name.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{migration, repository};
+
+    fn test_db() -> Connection {
+        let mut db = Connection::open_in_memory().unwrap();
+        migration::migrate(&mut db).unwrap();
+        db.execute_batch(
+            "INSERT INTO sites (name) VALUES ('youtube.com'), ('m.youtube.com'), ('youtu.be');
+             INSERT INTO articles (url, title, site_id) VALUES ('https://youtube.com/1', 'one', 1);
+             INSERT INTO articles (url, title, site_id) VALUES ('https://m.youtube.com/2', 'two', 2);
+             INSERT INTO articles (url, title, site_id) VALUES ('https://youtu.be/3', 'three', 3);",
+        )
+        .unwrap();
+        db
+    }
+
+    #[test]
+    fn test_merge_sites() {
+        let mut db = test_db();
+        merge_sites(
+            &db,
+            &["m.youtube.com".into(), "youtu.be".into()],
+            "youtube.com",
+        )
+        .unwrap();
+
+        let sites = list_sites(&db).unwrap();
+        assert_eq!(sites.len(), 1);
+        assert_eq!(
+            (sites[0].name.as_str(), sites[0].article_count),
+            ("youtube.com", 3)
+        );
+
+        // 統合元のホストは別名として残る
+        assert_eq!(find_site_id(&db, "YOUTU.BE").unwrap(), Some(1));
+        assert_eq!(list_aliases(&db).unwrap().len(), 2);
+
+        // 存在しないサイトが含まれていれば何も変わらない
+        assert!(repository::write(&mut db, |db| {
+            merge_sites(db, &["nope".into()], "youtube.com")
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_rename_and_aliases() {
+        let db = test_db();
+        assert!(rename_site(&db, "youtu.be", "youtube.com").is_err());
+        rename_site(&db, "youtu.be", "Youtu.be.").unwrap();
+
+        // 既存のサイト名は別名にできない
+        assert!(add_alias(&db, "m.youtube.com", "youtube.com").is_err());
+        add_alias(&db, "music.youtube.com", "youtube.com").unwrap();
+        assert_eq!(find_site_id(&db, "music.youtube.com").unwrap(), Some(1));
+
+        remove_alias(&db, "music.youtube.com").unwrap();
+        assert!(remove_alias(&db, "music.youtube.com").is_err());
+        assert_eq!(find_site_id(&db, "music.youtube.com").unwrap(), None);
+    }
+}
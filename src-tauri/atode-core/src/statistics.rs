@@ -0,0 +1,391 @@
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, TimeZone};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::articles::TagCount;
+use crate::dates;
+
+const DEFAULT_WINDOW_DAYS: u32 = 30;
+const DEFAULT_TOP_SITES: usize = 10;
+const TOP_TAGS_PER_WEEK: usize = 3;
+
+// 積読の経過日数の区分（上限日数, 表示名）
+const AGE_BUCKETS: &[(Option<i64>, &str)] = &[
+    (Some(7), "1週間以内"),
+    (Some(30), "1か月以内"),
+    (Some(90), "3か月以内"),
+    (Some(180), "半年以内"),
+    (Some(365), "1年以内"),
+    (None, "1年以上"),
+];
+
+/// 統計の集計条件（すべて省略可）
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct StatisticsOptions {
+    // 集計期間（今日を含む日数。既定は30日）
+    pub days: Option<u32>,
+    // 「読んだ」とみなすステータス（既定は done）
+    pub read_statuses: Option<Vec<String>>,
+    // サイトの上位件数（既定は10件）
+    pub top_sites: Option<usize>,
+}
+
+/// 読書状況の統計（日付はローカルタイム）
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Statistics {
+    pub days: u32,
+    pub saved_per_day: Vec<PeriodCount>,
+    pub saved_per_week: Vec<PeriodCount>,
+    // 集計期間内に保存した記事のうち読んだ割合
+    pub read_ratio: ReadRatio,
+    // 全期間の読んだ割合
+    pub overall_read_ratio: ReadRatio,
+    pub top_sites: Vec<SiteCount>,
+    pub tag_growth: Vec<TagGrowth>,
+    // 未読記事の経過日数の分布
+    pub backlog_age: Vec<AgeBucket>,
+}
+
+/// 日・週ごとの件数（`period`は日付、週なら月曜日の日付）
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PeriodCount {
+    pub period: String,
+    pub count: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadRatio {
+    pub saved: u32,
+    pub read: u32,
+    pub ratio: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SiteCount {
+    pub site: String,
+    pub count: u32,
+}
+
+/// 週ごとのタグの増え方
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagGrowth {
+    pub week: String,
+    pub new_tags: u32,
+    // 週末時点のタグ総数
+    pub total_tags: u32,
+    // その週に保存した記事によく付いたタグ
+    pub top_tags: Vec<TagCount>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AgeBucket {
+    pub label: String,
+    pub count: u32,
+}
+
+struct ArticleRow {
+    saved_on: NaiveDate,
+    site: Option<String>,
+    read: bool,
+}
+
+/// 現在のローカルタイムゾーンで統計を集計する
+pub fn statistics(db: &Connection, options: &StatisticsOptions) -> Result<Statistics, String> {
+    collect_statistics(db, options, &Local::now())
+}
+
+/// `now`のタイムゾーンで日付を区切って統計を集計する
+pub fn collect_statistics<Tz: TimeZone>(
+    db: &Connection,
+    options: &StatisticsOptions,
+    now: &DateTime<Tz>,
+) -> Result<Statistics, String> {
+    let tz = now.timezone();
+    let local_date = |value: &str| {
+        dates::parse_db_timestamp(value).map(|instant| instant.with_timezone(&tz).date_naive())
+    };
+
+    let days = options.days.unwrap_or(DEFAULT_WINDOW_DAYS).max(1);
+    let today = now.date_naive();
+    let first_day = today
+        .checked_sub_days(Days::new(u64::from(days - 1)))
+        .ok_or("集計期間が長すぎます")?;
+    let first_week = week_start(first_day);
+    let read_statuses: Vec<String> = options.read_statuses.as_ref().map_or_else(
+        || vec!["done".to_string()],
+        |statuses| statuses.iter().map(|s| s.trim().to_lowercase()).collect(),
+    );
+
+    // ゴミ箱内を除く全記事
+    let mut stmt = db
+        .prepare(
+            "SELECT a.created_at, s.name, LOWER(st.name)
+            FROM articles a
+            LEFT JOIN sites s ON a.site_id = s.id
+            LEFT JOIN statuses st ON a.status_id = st.id
+            WHERE a.deleted_at IS NULL",
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+    let mut articles = Vec::new();
+    for row in rows {
+        let (created_at, site, status) = row.map_err(|e| e.to_string())?;
+        articles.push(ArticleRow {
+            saved_on: local_date(&created_at)?,
+            site,
+            read: status.is_some_and(|status| read_statuses.contains(&status)),
+        });
+    }
+    let in_window: Vec<&ArticleRow> = articles
+        .iter()
+        .filter(|article| article.saved_on >= first_day)
+        .collect();
+
+    // 日・週ごとの保存数（0件の日も含める）
+    let mut per_day: BTreeMap<NaiveDate, u32> = first_day
+        .iter_days()
+        .take_while(|day| *day <= today)
+        .map(|day| (day, 0))
+        .collect();
+    let mut per_week: BTreeMap<NaiveDate, u32> = first_week
+        .iter_weeks()
+        .take_while(|week| *week <= today)
+        .map(|week| (week, 0))
+        .collect();
+    for article in &articles {
+        if let Some(count) = per_day.get_mut(&article.saved_on) {
+            *count += 1;
+        }
+        if let Some(count) = per_week.get_mut(&week_start(article.saved_on)) {
+            *count += 1;
+        }
+    }
+
+    let tag_growth = tag_growth(db, &per_week, &local_date)?;
+
+    Ok(Statistics {
+        days,
+        saved_per_day: period_counts(per_day),
+        saved_per_week: period_counts(per_week),
+        read_ratio: read_ratio(in_window.iter().copied()),
+        overall_read_ratio: read_ratio(articles.iter()),
+        top_sites: top_sites(&in_window, options.top_sites.unwrap_or(DEFAULT_TOP_SITES)),
+        tag_growth,
+        backlog_age: backlog_age(&articles, today),
+    })
+}
+
+// 集計期間内のサイト上位
+fn top_sites(articles: &[&ArticleRow], limit: usize) -> Vec<SiteCount> {
+    let mut sites: HashMap<&str, u32> = HashMap::new();
+    for article in articles {
+        *sites
+            .entry(article.site.as_deref().unwrap_or(""))
+            .or_default() += 1;
+    }
+    let mut top_sites: Vec<SiteCount> = sites
+        .into_iter()
+        .map(|(site, count)| SiteCount {
+            site: site.to_string(),
+            count,
+        })
+        .collect();
+    top_sites.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.site.cmp(&b.site)));
+    top_sites.truncate(limit);
+    top_sites
+}
+
+// 未読記事の経過日数の分布
+fn backlog_age(articles: &[ArticleRow], today: NaiveDate) -> Vec<AgeBucket> {
+    let mut buckets: Vec<AgeBucket> = AGE_BUCKETS
+        .iter()
+        .map(|(_, label)| AgeBucket {
+            label: (*label).to_string(),
+            count: 0,
+        })
+        .collect();
+    for article in articles.iter().filter(|article| !article.read) {
+        let age = (today - article.saved_on).num_days();
+        let index = AGE_BUCKETS
+            .iter()
+            .position(|(max_days, _)| max_days.is_none_or(|max_days| age <= max_days))
+            .unwrap_or(AGE_BUCKETS.len() - 1);
+        buckets[index].count += 1;
+    }
+    buckets
+}
+
+// 週ごとのタグ数の推移と、その週の記事によく付いたタグ
+fn tag_growth(
+    db: &Connection,
+    weeks: &BTreeMap<NaiveDate, u32>,
+    local_date: &dyn Fn(&str) -> Result<NaiveDate, String>,
+) -> Result<Vec<TagGrowth>, String> {
+    let Some(first_week) = weeks.keys().next().copied() else {
+        return Ok(Vec::new());
+    };
+
+    let mut stmt = db
+        .prepare("SELECT created_at FROM tags")
+        .map_err(|e| e.to_string())?;
+    let created = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| e.to_string())?
+        .map(|value| local_date(&value.map_err(|e| e.to_string())?))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut stmt = db
+        .prepare(
+            "SELECT a.created_at, t.name
+            FROM article_tags at
+            JOIN articles a ON at.article_id = a.id
+            JOIN tags t ON at.tag_id = t.id
+            WHERE a.deleted_at IS NULL",
+        )
+        .map_err(|e| e.to_string())?;
+    let mut used: HashMap<NaiveDate, HashMap<String, u32>> = HashMap::new();
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| e.to_string())?;
+    for row in rows {
+        let (created_at, tag) = row.map_err(|e| e.to_string())?;
+        let week = week_start(local_date(&created_at)?);
+        if week >= first_week {
+            *used.entry(week).or_default().entry(tag).or_default() += 1;
+        }
+    }
+
+    Ok(weeks
+        .keys()
+        .map(|week| {
+            let week_end = week.checked_add_days(Days::new(7)).unwrap_or(*week);
+            let mut top_tags: Vec<TagCount> = used
+                .remove(week)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(tag, count)| TagCount { tag, count })
+                .collect();
+            top_tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+            top_tags.truncate(TOP_TAGS_PER_WEEK);
+
+            TagGrowth {
+                week: week.to_string(),
+                new_tags: count_u32(created.iter().filter(|day| week_start(**day) == *week)),
+                total_tags: count_u32(created.iter().filter(|day| **day < week_end)),
+                top_tags,
+            }
+        })
+        .collect())
+}
+
+fn read_ratio<'a>(articles: impl Iterator<Item = &'a ArticleRow>) -> ReadRatio {
+    let (saved, read) = articles.fold((0, 0), |(saved, read), article| {
+        (saved + 1, read + u32::from(article.read))
+    });
+    ReadRatio {
+        saved,
+        read,
+        ratio: if saved == 0 {
+            0.0
+        } else {
+            f64::from(read) / f64::from(saved)
+        },
+    }
+}
+
+fn period_counts(counts: BTreeMap<NaiveDate, u32>) -> Vec<PeriodCount> {
+    counts
+        .into_iter()
+        .map(|(period, count)| PeriodCount {
+            period: period.to_string(),
+            count,
+        })
+        .collect()
+}
+
+fn count_u32<T>(items: impl Iterator<Item = T>) -> u32 {
+    u32::try_from(items.count()).unwrap_or(u32::MAX)
+}
+
+// 週の始まり（月曜日）
+fn week_start(day: NaiveDate) -> NaiveDate {
+    day.checked_sub_days(Days::new(u64::from(day.weekday().num_days_from_monday())))
+        .unwrap_or(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init_database;
+
+    #[test]
+    fn test_collect_statistics() {
+        let db = init_database(":memory:").unwrap();
+        db.execute_batch(
+            "INSERT INTO sites (name) VALUES ('zenn.dev'), ('qiita.com');
+             INSERT INTO articles (url, title, site_id, created_at) VALUES
+               ('https://zenn.dev/a', 'A', 1, '2026-03-04 16:00:00'),
+               ('https://zenn.dev/b', 'B', 1, '2026-03-03 10:00:00'),
+               ('https://qiita.com/c', 'C', 2, '2026-02-20 10:00:00'),
+               ('https://qiita.com/d', 'D', 2, '2025-01-01 10:00:00');
+             UPDATE articles SET status_id = (SELECT id FROM statuses WHERE name = 'done')
+             WHERE url IN ('https://zenn.dev/b', 'https://qiita.com/d');
+             INSERT INTO tags (name, created_at) VALUES
+               ('rust', '2025-01-01 10:00:00'),
+               ('async', '2026-03-03 10:00:00');
+             INSERT INTO article_tags (article_id, tag_id) VALUES (1, 1), (1, 2), (2, 1), (4, 1);",
+        )
+        .unwrap();
+
+        // 2026-03-05（木）10:00 JST
+        let now = DateTime::parse_from_rfc3339("2026-03-05T10:00:00+09:00").unwrap();
+        let options = StatisticsOptions {
+            days: Some(7),
+            ..Default::default()
+        };
+        let stats = collect_statistics(&db, &options, &now).unwrap();
+
+        // 3/4 16:00 UTC はJSTでは3/5
+        assert_eq!(stats.saved_per_day.len(), 7);
+        let last = stats.saved_per_day.last().unwrap();
+        assert_eq!((last.period.as_str(), last.count), ("2026-03-05", 1));
+        assert_eq!(stats.saved_per_day.iter().map(|d| d.count).sum::<u32>(), 2);
+
+        // 2/27〜3/5 は2/23週と3/2週にまたがる
+        let weeks: Vec<_> = stats
+            .saved_per_week
+            .iter()
+            .map(|w| (w.period.as_str(), w.count))
+            .collect();
+        assert_eq!(weeks, [("2026-02-23", 0), ("2026-03-02", 2)]);
+
+        assert_eq!((stats.read_ratio.saved, stats.read_ratio.read), (2, 1));
+        assert!((stats.overall_read_ratio.ratio - 0.5).abs() < f64::EPSILON);
+
+        assert_eq!(stats.top_sites.len(), 1);
+        assert_eq!(
+            (stats.top_sites[0].site.as_str(), stats.top_sites[0].count),
+            ("zenn.dev", 2)
+        );
+
+        let growth = stats.tag_growth.last().unwrap();
+        assert_eq!((growth.new_tags, growth.total_tags), (1, 2));
+        assert_eq!(growth.top_tags[0].tag, "rust");
+        assert_eq!(growth.top_tags[0].count, 2);
+
+        // 未読は A（当日）と C（13日前）
+        let age: Vec<u32> = stats.backlog_age.iter().map(|b| b.count).collect();
+        assert_eq!(age, [1, 1, 0, 0, 0, 0]);
+    }
+}
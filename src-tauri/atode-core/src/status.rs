@@ -0,0 +1,339 @@
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::articles::timestamp_from_row;
+
+/// 読書ステータス（inbox / reading / done / reference など）
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Status {
+    pub id: i64,
+    pub name: String,
+    pub position: i64,
+    pub article_count: u32,
+}
+
+/// ステータス変更履歴の1件
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatusChange {
+    pub status: Option<String>,
+    // RFC 3339（UTC）
+    pub changed_at: DateTime<Utc>,
+}
+
+/// ステータス一覧（表示順、各ステータスの記事数付き。ゴミ箱内の記事は数えない）
+pub fn list_statuses(db: &Connection) -> Result<Vec<Status>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT
+               st.id
+             , st.name
+             , st.position
+             , COUNT(a.id) as article_count
+            FROM statuses st
+            LEFT JOIN articles a
+              ON a.status_id = st.id
+             AND a.deleted_at IS NULL
+            GROUP BY st.id
+            ORDER BY st.position, st.id",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let statuses = stmt
+        .query_map([], |row| {
+            Ok(Status {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                position: row.get(2)?,
+                article_count: row.get(3)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    statuses
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+/// ステータスを末尾に追加
+pub fn create_status(db: &Connection, name: &str) -> Result<i64, String> {
+    let name = validate_name(name)?;
+    if find_status_id(db, name)?.is_some() {
+        return Err(format!("ステータス「{name}」は既に存在します"));
+    }
+
+    db.execute(
+        "INSERT INTO statuses (name, position)
+         VALUES (?, (SELECT COALESCE(MAX(position), -1) + 1 FROM statuses))",
+        params![name],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let status_id = db.last_insert_rowid();
+    log!("新規ステータス作成: {name} (ID: {status_id})");
+    Ok(status_id)
+}
+
+/// ステータス名を変更
+pub fn rename_status(db: &Connection, name: &str, new_name: &str) -> Result<(), String> {
+    let new_name = validate_name(new_name)?;
+    let status_id = get_status_id(db, name)?;
+    if let Some(other_id) = find_status_id(db, new_name)? {
+        if other_id != status_id {
+            return Err(format!("ステータス「{new_name}」は既に存在します"));
+        }
+    }
+
+    db.execute(
+        "UPDATE statuses SET name = ? WHERE id = ?",
+        params![new_name, status_id],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// ステータスを削除（属する記事は`move_to`、未指定なら残りの先頭ステータスへ移す）
+pub fn delete_status(db: &Connection, name: &str, move_to: Option<&str>) -> Result<(), String> {
+    let status_id = get_status_id(db, name)?;
+
+    let move_to_id = match move_to {
+        Some(move_to) => get_status_id(db, move_to)?,
+        None => db
+            .query_row(
+                "SELECT id FROM statuses WHERE id != ? ORDER BY position, id LIMIT 1",
+                [status_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?
+            .ok_or("最後のステータスは削除できません")?,
+    };
+    if move_to_id == status_id {
+        return Err("削除するステータスと移動先が同じです".to_string());
+    }
+
+    db.execute(
+        "UPDATE articles SET status_id = ? WHERE status_id = ?",
+        params![move_to_id, status_id],
+    )
+    .map_err(|e| e.to_string())?;
+    db.execute("DELETE FROM statuses WHERE id = ?", [status_id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// ステータスの表示順を指定順に並べ替え（先頭が新規記事の初期ステータスになる）
+///
+/// 指定しなかったステータスは、今の順序のまま指定したものの後ろに並べる
+pub fn reorder_statuses(db: &Connection, names: &[String]) -> Result<(), String> {
+    let mut ordered: Vec<i64> = Vec::new();
+    for name in names {
+        let status_id = get_status_id(db, name)?;
+        if ordered.contains(&status_id) {
+            return Err(format!("ステータス「{name}」が重複しています"));
+        }
+        ordered.push(status_id);
+    }
+
+    let mut stmt = db
+        .prepare("SELECT id FROM statuses ORDER BY position, id")
+        .map_err(|e| e.to_string())?;
+    let current = stmt
+        .query_map([], |row| row.get::<_, i64>(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    for status_id in current {
+        if !ordered.contains(&status_id) {
+            ordered.push(status_id);
+        }
+    }
+
+    for (position, status_id) in ordered.into_iter().enumerate() {
+        db.execute(
+            "UPDATE statuses SET position = ? WHERE id = ?",
+            params![i64::try_from(position).unwrap_or(i64::MAX), status_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// 記事のステータスを変更（変更日時・履歴はトリガーで記録される）
+pub fn set_article_status(db: &Connection, article_id: i64, status: &str) -> Result<(), String> {
+    let status_id = get_status_id(db, status)?;
+
+    let rows = db
+        .execute(
+            "UPDATE articles SET status_id = ? WHERE id = ?",
+            params![status_id, article_id],
+        )
+        .map_err(|e| e.to_string())?;
+    if rows == 0 {
+        return Err(format!("記事が見つかりません (ID: {article_id})"));
+    }
+
+    log!("記事ステータス変更: ID {article_id} → {status}");
+    Ok(())
+}
+
+/// 記事のステータス変更履歴（新しい順）
+pub fn article_status_history(
+    db: &Connection,
+    article_id: i64,
+) -> Result<Vec<StatusChange>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT st.name, h.changed_at
+            FROM article_status_history h
+            LEFT JOIN statuses st
+              ON h.status_id = st.id
+            WHERE h.article_id = ?
+            ORDER BY h.changed_at DESC, h.id DESC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let history = stmt
+        .query_map([article_id], |row| {
+            Ok(StatusChange {
+                status: row.get(0)?,
+                changed_at: timestamp_from_row(1, &row.get::<_, String>(1)?)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    history
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+fn get_status_id(db: &Connection, name: &str) -> Result<i64, String> {
+    find_status_id(db, name)?.ok_or_else(|| format!("ステータス「{name}」が見つかりません"))
+}
+
+fn find_status_id(db: &Connection, name: &str) -> Result<Option<i64>, String> {
+    db.query_row(
+        "SELECT id FROM statuses WHERE name = ? COLLATE NOCASE",
+        [name.trim()],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+fn validate_name(name: &str) -> Result<&str, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("ステータス名が空です".to_string());
+    }
+    Ok(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::migration;
+
+    fn test_db() -> Connection {
+        let mut db = Connection::open_in_memory().unwrap();
+        migration::migrate(&mut db).unwrap();
+        db.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
+        db.execute(
+            "INSERT INTO articles (url, title) VALUES ('https://example.com/', 'Example')",
+            [],
+        )
+        .unwrap();
+        db
+    }
+
+    fn status_of(db: &Connection, article_id: i64) -> String {
+        db.query_row(
+            "SELECT st.name FROM articles a JOIN statuses st ON a.status_id = st.id WHERE a.id = ?",
+            [article_id],
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_new_article_starts_in_inbox() {
+        let db = test_db();
+        assert_eq!(status_of(&db, 1), "inbox");
+
+        let history = article_status_history(&db, 1).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].status.as_deref(), Some("inbox"));
+    }
+
+    #[test]
+    fn test_set_article_status_records_history() {
+        let db = test_db();
+        set_article_status(&db, 1, "reading").unwrap();
+        set_article_status(&db, 1, "Done").unwrap();
+        // 同じステータスへの変更は履歴に残らない
+        set_article_status(&db, 1, "done").unwrap();
+
+        assert_eq!(status_of(&db, 1), "done");
+        let history: Vec<Option<String>> = article_status_history(&db, 1)
+            .unwrap()
+            .into_iter()
+            .map(|change| change.status)
+            .collect();
+        assert_eq!(
+            history,
+            [
+                Some("done".into()),
+                Some("reading".into()),
+                Some("inbox".into())
+            ]
+        );
+
+        assert!(set_article_status(&db, 1, "no-such-status").is_err());
+        assert!(set_article_status(&db, 99, "done").is_err());
+    }
+
+    #[test]
+    fn test_user_defined_statuses() {
+        let db = test_db();
+        create_status(&db, "later").unwrap();
+        assert!(create_status(&db, "LATER").is_err());
+
+        // 並べ替えで先頭になったステータスが初期ステータスになる
+        reorder_statuses(&db, &["later".into(), "inbox".into()]).unwrap();
+        db.execute(
+            "INSERT INTO articles (url, title) VALUES ('https://example.com/2', 'Second')",
+            [],
+        )
+        .unwrap();
+        assert_eq!(status_of(&db, 2), "later");
+
+        // 指定しなかったステータスは後ろに今の順序で並び、表示順は重複しない
+        let names: Vec<String> = list_statuses(&db)
+            .unwrap()
+            .into_iter()
+            .map(|status| status.name)
+            .collect();
+        assert_eq!(names, ["later", "inbox", "reading", "done", "reference"]);
+        let distinct: i64 = db
+            .query_row("SELECT COUNT(DISTINCT position) FROM statuses", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(distinct, 5);
+        assert!(reorder_statuses(&db, &["inbox".into(), "inbox".into()]).is_err());
+
+        rename_status(&db, "later", "someday").unwrap();
+        assert_eq!(status_of(&db, 2), "someday");
+
+        // 削除すると記事は移動先へ
+        delete_status(&db, "someday", Some("reference")).unwrap();
+        assert_eq!(status_of(&db, 2), "reference");
+
+        let names: Vec<String> = list_statuses(&db)
+            .unwrap()
+            .into_iter()
+            .map(|status| status.name)
+            .collect();
+        assert_eq!(names, ["inbox", "reading", "done", "reference"]);
+    }
+}
@@ -0,0 +1,156 @@
+use rusqlite::Connection;
+
+use crate::articles::{
+    self, ArticlePage, ArticleWithDetails, SaveArticleRequest, SearchFilters, TagCount,
+    UpdateResult,
+};
+use crate::resave::{ResavePolicy, SaveResult};
+use crate::url_rules::UrlRules;
+use crate::{init_database, repository, trash};
+
+/// 記事の保存先（GUI・CLI・スクリプトから同じ操作で記事を扱う）
+///
+/// 書き込みはそれぞれ1操作として扱い、失敗したら何も変えない
+pub trait ArticleStore {
+    /// 記事を保存する（正規形が同じURLの記事があれば再保存として扱う）
+    fn save_article(&mut self, request: SaveArticleRequest) -> Result<SaveResult, String>;
+
+    /// 記事を編集する（タグは指定したものに置き換える）。
+    /// URLが別の記事と重なる場合、`merge`ならその記事を統合し、そうでなければ`Conflict`を返す
+    fn update_article(
+        &mut self,
+        id: i64,
+        request: &SaveArticleRequest,
+        merge: bool,
+    ) -> Result<UpdateResult, String>;
+
+    /// 記事をゴミ箱へ移す
+    fn delete_article(&mut self, id: i64) -> Result<(), String>;
+
+    /// 複数の記事をまとめてゴミ箱へ移す（見つからない・重複したIDがあれば何も移さない）
+    fn delete_articles(&mut self, ids: &[i64]) -> Result<(), String>;
+
+    /// IDで記事を取得する（ゴミ箱内の記事は`None`）
+    fn get_article(&self, id: i64) -> Result<Option<ArticleWithDetails>, String>;
+
+    /// 条件に一致する記事（1ページ分と全件数）
+    fn search_articles(&self, filters: &SearchFilters) -> Result<ArticlePage, String>;
+
+    /// よく使うタグ（`status`指定時はそのステータスの記事のみで集計）
+    fn popular_tags(&self, limit: usize, status: Option<&str>) -> Result<Vec<TagCount>, String>;
+}
+
+/// SQLiteの記事データベース（アプリ本体と同じ`atode.db`）
+pub struct SqliteStore {
+    db: Connection,
+    url_rules: UrlRules,
+    resave_policy: ResavePolicy,
+}
+
+impl SqliteStore {
+    pub const fn new(db: Connection, url_rules: UrlRules, resave_policy: ResavePolicy) -> Self {
+        Self {
+            db,
+            url_rules,
+            resave_policy,
+        }
+    }
+
+    /// データベースを開く（スキーマは最新化する）
+    pub fn open(
+        path: &str,
+        url_rules: UrlRules,
+        resave_policy: ResavePolicy,
+    ) -> Result<Self, String> {
+        Ok(Self::new(init_database(path)?, url_rules, resave_policy))
+    }
+
+    /// 読み取り用の接続（ステータス・タグ階層など、`ArticleStore`に無い操作に使う）
+    pub const fn connection(&self) -> &Connection {
+        &self.db
+    }
+
+    /// `ArticleStore`に無い書き込み操作を1トランザクションで実行する
+    pub fn write<T>(
+        &mut self,
+        operation: impl FnOnce(&Connection) -> Result<T, String>,
+    ) -> Result<T, String> {
+        repository::write(&mut self.db, operation)
+    }
+
+    pub const fn url_rules(&self) -> &UrlRules {
+        &self.url_rules
+    }
+}
+
+impl ArticleStore for SqliteStore {
+    fn save_article(&mut self, request: SaveArticleRequest) -> Result<SaveResult, String> {
+        let (url_rules, resave_policy) = (&self.url_rules, &self.resave_policy);
+        repository::write(&mut self.db, |db| {
+            articles::store_article(db, url_rules, resave_policy, request)
+        })
+    }
+
+    fn update_article(
+        &mut self,
+        id: i64,
+        request: &SaveArticleRequest,
+        merge: bool,
+    ) -> Result<UpdateResult, String> {
+        let url_rules = &self.url_rules;
+        repository::write(&mut self.db, |db| {
+            articles::edit_article(db, url_rules, id, request, merge)
+        })
+    }
+
+    fn delete_article(&mut self, id: i64) -> Result<(), String> {
+        self.write(|db| trash::move_to_trash(db, id))
+    }
+
+    fn delete_articles(&mut self, ids: &[i64]) -> Result<(), String> {
+        self.write(|db| ids.iter().try_for_each(|&id| trash::move_to_trash(db, id)))
+    }
+
+    fn get_article(&self, id: i64) -> Result<Option<ArticleWithDetails>, String> {
+        articles::get_article(&self.db, id)
+    }
+
+    fn search_articles(&self, filters: &SearchFilters) -> Result<ArticlePage, String> {
+        articles::query_articles(&self.db, filters)
+    }
+
+    fn popular_tags(&self, limit: usize, status: Option<&str>) -> Result<Vec<TagCount>, String> {
+        articles::popular_tags(&self.db, limit, status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn save(store: &mut SqliteStore, url: &str) -> i64 {
+        let request = SaveArticleRequest {
+            url: url.to_string(),
+            title: "title".to_string(),
+            tags: None,
+        };
+        store.save_article(request).unwrap().article_id
+    }
+
+    #[test]
+    fn test_delete_articles_is_all_or_nothing() {
+        let mut store =
+            SqliteStore::open(":memory:", UrlRules::default(), ResavePolicy::default()).unwrap();
+        let a = save(&mut store, "https://example.com/a");
+        let b = save(&mut store, "https://example.com/b");
+
+        // 2件目で失敗しても、1件目はゴミ箱へ移らない
+        assert!(store.delete_articles(&[a, a]).is_err());
+        assert!(store.delete_articles(&[a, 99]).is_err());
+        assert!(store.get_article(a).unwrap().is_some());
+
+        store.delete_articles(&[a, b]).unwrap();
+        assert!(store.get_article(a).unwrap().is_none());
+        assert!(store.get_article(b).unwrap().is_none());
+    }
+}
@@ -0,0 +1,218 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::tags;
+
+/// 一緒に付いていることの多いタグ
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RelatedTag {
+    pub tag: String,
+    /// 両方のタグが付いた記事数
+    pub count: u32,
+    /// Jaccard係数（両方付いた記事数 / どちらかが付いた記事数）
+    pub score: f64,
+}
+
+/// タグの共起グラフ
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagGraph {
+    pub nodes: Vec<TagGraphNode>,
+    pub edges: Vec<TagGraphEdge>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagGraphNode {
+    pub id: i64,
+    pub name: String,
+    pub article_count: u32,
+}
+
+/// 2つのタグが同じ記事に付いている回数（`source` < `target`）
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagGraphEdge {
+    pub source: i64,
+    pub target: i64,
+    pub weight: u32,
+}
+
+/// `tag`と一緒に付いていることの多いタグ（共起回数の多い順。ゴミ箱内の記事は数えない）
+/// 未登録のタグなら空
+pub fn related_tags(db: &Connection, tag: &str, limit: usize) -> Result<Vec<RelatedTag>, String> {
+    let Some(tag_id) = tags::find_tag_id(db, tag)? else {
+        return Ok(Vec::new());
+    };
+
+    let mut stmt = db
+        .prepare(
+            "WITH live AS (
+               SELECT at.article_id, at.tag_id
+               FROM article_tags at
+               JOIN articles a
+                 ON at.article_id = a.id
+               WHERE a.deleted_at IS NULL
+             ),
+             totals AS (
+               SELECT tag_id, COUNT(*) as total FROM live GROUP BY tag_id
+             )
+             SELECT
+               t.name
+             , COUNT(*) as count
+             , CAST(COUNT(*) AS REAL)
+                 / (base_total.total + other_total.total - COUNT(*)) as score
+            FROM live base
+            JOIN live other
+              ON other.article_id = base.article_id
+             AND other.tag_id != base.tag_id
+            JOIN tags t
+              ON other.tag_id = t.id
+            JOIN totals base_total
+              ON base_total.tag_id = base.tag_id
+            JOIN totals other_total
+              ON other_total.tag_id = other.tag_id
+            WHERE base.tag_id = ?1
+              AND t.name != ?3
+            GROUP BY t.id
+            ORDER BY count DESC, score DESC, t.name
+            LIMIT ?2",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let related = stmt
+        .query_map(params![tag_id, limit, tags::EXCLUDED_FROM_STATS], |row| {
+            Ok(RelatedTag {
+                tag: row.get(0)?,
+                count: row.get(1)?,
+                score: row.get(2)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    related
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+/// タグの共起グラフ（`min_weight`回以上一緒に付いたタグの組を辺にする）
+/// ノードは記事の付いているタグのみ
+pub fn tag_graph(db: &Connection, min_weight: u32) -> Result<TagGraph, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT t.id, t.name, COUNT(*) as article_count
+            FROM tags t
+            JOIN article_tags at
+              ON at.tag_id = t.id
+            JOIN articles a
+              ON at.article_id = a.id
+            WHERE a.deleted_at IS NULL
+              AND t.name != ?
+            GROUP BY t.id
+            ORDER BY article_count DESC, t.name",
+        )
+        .map_err(|e| e.to_string())?;
+    let nodes = stmt
+        .query_map([tags::EXCLUDED_FROM_STATS], |row| {
+            Ok(TagGraphNode {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                article_count: row.get(2)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut stmt = db
+        .prepare(
+            "SELECT x.tag_id, y.tag_id, COUNT(*) as weight
+            FROM article_tags x
+            JOIN article_tags y
+              ON y.article_id = x.article_id
+             AND x.tag_id < y.tag_id
+            JOIN articles a
+              ON x.article_id = a.id
+            JOIN tags tx
+              ON x.tag_id = tx.id
+            JOIN tags ty
+              ON y.tag_id = ty.id
+            WHERE a.deleted_at IS NULL
+              AND tx.name != ?1
+              AND ty.name != ?1
+            GROUP BY x.tag_id, y.tag_id
+            HAVING COUNT(*) >= ?2
+            ORDER BY weight DESC, x.tag_id, y.tag_id",
+        )
+        .map_err(|e| e.to_string())?;
+    let edges = stmt
+        .query_map(
+            params![tags::EXCLUDED_FROM_STATS, min_weight.max(1)],
+            |row| {
+                Ok(TagGraphEdge {
+                    source: row.get(0)?,
+                    target: row.get(1)?,
+                    weight: row.get(2)?,
+                })
+            },
+        )
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(TagGraph { nodes, edges })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::migration;
+
+    fn test_db() -> Connection {
+        let mut db = Connection::open_in_memory().unwrap();
+        migration::migrate(&mut db).unwrap();
+        db.execute_batch(
+            "INSERT INTO tags (name) VALUES ('rust'), ('async'), ('tokio'), ('python'), ('auto-saved');
+             INSERT INTO articles (url, title) VALUES ('https://example.com/1', 'one');
+             INSERT INTO articles (url, title) VALUES ('https://example.com/2', 'two');
+             INSERT INTO articles (url, title) VALUES ('https://example.com/3', 'three');
+             INSERT INTO articles (url, title, deleted_at)
+               VALUES ('https://example.com/4', 'trashed', CURRENT_TIMESTAMP);
+             INSERT INTO article_tags (article_id, tag_id) VALUES
+               (1, 1), (1, 2), (1, 3),
+               (2, 1), (2, 2), (2, 5),
+               (3, 4),
+               (4, 1), (4, 4);",
+        )
+        .unwrap();
+        db
+    }
+
+    #[test]
+    fn test_related_tags() {
+        let db = test_db();
+        let related = related_tags(&db, "Rust", 10).unwrap();
+        let names: Vec<(&str, u32)> = related.iter().map(|r| (r.tag.as_str(), r.count)).collect();
+        // ゴミ箱内の記事の python・自動保存の目印の auto-saved は数えない
+        assert_eq!(names, [("async", 2), ("tokio", 1)]);
+        assert!((related[0].score - 1.0).abs() < f64::EPSILON);
+        assert!((related[1].score - 0.5).abs() < f64::EPSILON);
+
+        assert!(related_tags(&db, "unknown", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_tag_graph() {
+        let db = test_db();
+        let graph = tag_graph(&db, 1).unwrap();
+        // auto-saved は関連タグと同じくノード・辺に含めない
+        assert_eq!(graph.nodes.len(), 4);
+        assert!(graph.nodes.iter().all(|node| node.name != "auto-saved"));
+        let edges: Vec<(i64, i64, u32)> = graph
+            .edges
+            .iter()
+            .map(|e| (e.source, e.target, e.weight))
+            .collect();
+        assert_eq!(edges, [(1, 2, 2), (1, 3, 1), (2, 3, 1)]);
+
+        let graph = tag_graph(&db, 2).unwrap();
+        assert_eq!(graph.edges.len(), 1);
+    }
+}
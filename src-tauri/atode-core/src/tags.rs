@@ -0,0 +1,604 @@
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use unicode_normalization::UnicodeNormalization;
+
+/// 指定タグ（名前）とその子孫タグのIDを返すクエリ
+/// タグ名は正規化済み（`normalize_tag_name`/`canonical_name`）で渡す
+/// `UNION`で重複を除くため、万一親子関係が循環していても止まる
+pub const SUBTREE_SQL: &str = "WITH RECURSIVE subtree(id) AS (
+        SELECT id FROM tags WHERE name = ?
+        UNION
+        SELECT c.id FROM tags c JOIN subtree ON c.parent_id = subtree.id
+    )
+    SELECT id FROM subtree";
+
+/// 集計（よく使うタグ・関連タグ・共起グラフ）に含めないタグ（自動保存の目印）
+pub const EXCLUDED_FROM_STATS: &str = "auto-saved";
+
+/// タグの別名
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagAlias {
+    pub alias: String,
+    pub tag: String,
+}
+
+/// タグ階層の1ノード
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagNode {
+    pub id: i64,
+    pub name: String,
+    /// このタグが直接付いた記事数
+    pub article_count: u32,
+    /// 子孫タグを含めた記事数（重複なし）
+    pub total_count: u32,
+    pub children: Vec<Self>,
+}
+
+/// タグ名の正規化（保存時は必ずこれを通す）
+/// NFKCで全角英数などを揃え、前後の空白を除き（連続する空白は1つにまとめる）、小文字化する
+pub fn normalize_tag_name(name: &str) -> String {
+    let name: String = name.nfkc().collect();
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// タグ名（または別名）から正式なタグIDを探す
+pub fn find_tag_id(db: &Connection, name: &str) -> Result<Option<i64>, String> {
+    db.query_row(
+        "SELECT id FROM tags WHERE name = ?1
+         UNION ALL
+         SELECT tag_id FROM tag_aliases WHERE alias = ?1
+         LIMIT 1",
+        [normalize_tag_name(name)],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+/// 検索などで使う正式なタグ名（別名なら参照先、未登録なら正規化した名前）
+pub fn canonical_name(db: &Connection, name: &str) -> Result<String, String> {
+    let normalized = normalize_tag_name(name);
+    db.query_row(
+        "SELECT t.name FROM tag_aliases al JOIN tags t ON al.tag_id = t.id WHERE al.alias = ?",
+        [&normalized],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+    .map(|name| name.unwrap_or(normalized))
+}
+
+/// タグの親を設定する（子孫タグもまとめて移動する）。`None`ならトップレベルへ
+pub fn set_tag_parent(db: &Connection, tag: &str, parent: Option<&str>) -> Result<(), String> {
+    let tag_id = get_tag_id(db, tag)?;
+
+    let parent_id = match parent {
+        Some(parent) => {
+            let parent_id = get_tag_id(db, parent)?;
+            // 自分自身や子孫の下には移動できない（循環防止）
+            if subtree_ids(db, tag)?.contains(&parent_id) {
+                return Err(format!(
+                    "タグ「{}」を自身の子孫「{}」の下へは移動できません",
+                    tag.trim(),
+                    parent.trim()
+                ));
+            }
+            Some(parent_id)
+        }
+        None => None,
+    };
+
+    db.execute(
+        "UPDATE tags SET parent_id = ? WHERE id = ?",
+        params![parent_id, tag_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    log!(
+        "タグ階層変更: {} → {}",
+        tag.trim(),
+        parent.map_or("(トップレベル)", str::trim)
+    );
+    Ok(())
+}
+
+/// タグ名を変更する（新しい名前も正規化される）
+/// 既存の別タグと同じ名前にする場合は、そのタグへ統合する（`merge_tags`と同じ）
+pub fn rename_tag(db: &Connection, name: &str, new_name: &str) -> Result<(), String> {
+    let new_name = normalize_tag_name(new_name);
+    if new_name.is_empty() {
+        return Err("タグ名が空です".to_string());
+    }
+    let tag_id = get_tag_id(db, name)?;
+    if let Some(other_id) = find_tag_id(db, &new_name)? {
+        if other_id != tag_id {
+            return merge_tags(db, &[name.to_string()], &new_name);
+        }
+    }
+
+    db.execute(
+        "UPDATE tags SET name = ? WHERE id = ?",
+        params![new_name, tag_id],
+    )
+    .map_err(|e| e.to_string())?;
+    // 自分の別名を正式名にした場合、その別名は不要になる
+    db.execute("DELETE FROM tag_aliases WHERE alias = ?", [&new_name])
+        .map_err(|e| e.to_string())?;
+
+    log!("タグ名変更: {} → {new_name}", name.trim());
+    Ok(())
+}
+
+/// `sources`のタグを`target`へ統合し、統合元のタグを削除する
+/// 両方のタグが付いていた記事は1件にまとめ、統合元の子タグは`target`の子になる
+/// 統合元の名前は`target`の別名として残るので、以後その名前で保存しても`target`が付く
+pub fn merge_tags(db: &Connection, sources: &[String], target: &str) -> Result<(), String> {
+    let target_id = get_tag_id(db, target)?;
+
+    for source in sources {
+        let source_id = get_tag_id(db, source)?;
+        if source_id == target_id {
+            continue;
+        }
+
+        let source_name: String = db
+            .query_row("SELECT name FROM tags WHERE id = ?", [source_id], |row| {
+                row.get(0)
+            })
+            .map_err(|e| e.to_string())?;
+        merge_tag_ids(db, source_id, target_id)?;
+        db.execute(
+            "INSERT OR REPLACE INTO tag_aliases (alias, tag_id) VALUES (?, ?)",
+            params![source_name, target_id],
+        )
+        .map_err(|e| e.to_string())?;
+
+        log!("タグ統合: {} → {}", source.trim(), target.trim());
+    }
+
+    Ok(())
+}
+
+/// 既存のタグ名をすべて正規化する（正規化後に同じ名前になるタグは1つに統合）
+/// 正規化ルール導入前に作られたタグのための処理で、対象が無ければ何もしない。統合・変更したタグ数を返す
+pub fn normalize_existing_tags(db: &Connection) -> Result<usize, String> {
+    let mut stmt = db
+        .prepare("SELECT id, name FROM tags ORDER BY id")
+        .map_err(|e| e.to_string())?;
+    let tags = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    drop(stmt);
+
+    // 正規化後の名前ごとに残すタグを決める（既に正規化済みの名前があればそれ、無ければ最古のタグ）
+    let mut keep: HashMap<String, i64> = HashMap::new();
+    for (id, name) in &tags {
+        let normalized = normalize_tag_name(name);
+        if *name == normalized {
+            keep.insert(normalized, *id);
+        } else {
+            keep.entry(normalized).or_insert(*id);
+        }
+    }
+
+    let mut changed = 0;
+    for (id, name) in &tags {
+        let normalized = normalize_tag_name(name);
+        if *name == normalized {
+            continue;
+        }
+        let keep_id = keep[&normalized];
+        if keep_id == *id {
+            db.execute(
+                "UPDATE tags SET name = ? WHERE id = ?",
+                params![normalized, id],
+            )
+            .map_err(|e| e.to_string())?;
+        } else {
+            merge_tag_ids(db, *id, keep_id)?;
+        }
+        log!("タグ正規化: '{name}' → '{normalized}'");
+        changed += 1;
+    }
+
+    Ok(changed)
+}
+
+/// タグの別名一覧
+pub fn list_aliases(db: &Connection) -> Result<Vec<TagAlias>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT al.alias, t.name
+            FROM tag_aliases al
+            JOIN tags t
+              ON al.tag_id = t.id
+            ORDER BY t.name, al.alias",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let aliases = stmt
+        .query_map([], |row| {
+            Ok(TagAlias {
+                alias: row.get(0)?,
+                tag: row.get(1)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    aliases
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+/// 別名を登録する（既に登録済みの別名なら参照先を付け替える）
+pub fn add_alias(db: &Connection, alias: &str, tag: &str) -> Result<(), String> {
+    let alias = normalize_tag_name(alias);
+    if alias.is_empty() {
+        return Err("別名が空です".to_string());
+    }
+    let tag_id = get_tag_id(db, tag)?;
+
+    let existing_tag: Option<i64> = db
+        .query_row("SELECT id FROM tags WHERE name = ?", [&alias], |row| {
+            row.get(0)
+        })
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if existing_tag.is_some() {
+        return Err(format!(
+            "タグ「{alias}」が存在するため別名にできません（統合する場合はマージしてください）"
+        ));
+    }
+
+    db.execute(
+        "INSERT INTO tag_aliases (alias, tag_id) VALUES (?, ?)
+         ON CONFLICT(alias) DO UPDATE SET tag_id = excluded.tag_id",
+        params![alias, tag_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    log!("タグ別名登録: {alias} → {}", tag.trim());
+    Ok(())
+}
+
+/// 別名を削除する
+pub fn remove_alias(db: &Connection, alias: &str) -> Result<(), String> {
+    let alias = normalize_tag_name(alias);
+    let rows = db
+        .execute("DELETE FROM tag_aliases WHERE alias = ?", [&alias])
+        .map_err(|e| e.to_string())?;
+    if rows == 0 {
+        return Err(format!("別名「{alias}」が見つかりません"));
+    }
+    Ok(())
+}
+
+// タグを別のタグへ統合する（記事・子タグ・別名を付け替えて統合元を削除）
+fn merge_tag_ids(db: &Connection, source_id: i64, target_id: i64) -> Result<(), String> {
+    // 統合先が統合元の子孫なら、統合先を統合元の親の下へ付け替えて循環を防ぐ
+    if subtree_of(db, source_id)?.contains(&target_id) {
+        db.execute(
+            "UPDATE tags SET parent_id = (SELECT parent_id FROM tags WHERE id = ?1)
+             WHERE id = ?2",
+            params![source_id, target_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    // 記事-タグ関連を付け替え（主キーが重複するものは統合先側を残す）
+    db.execute(
+        "INSERT OR IGNORE INTO article_tags (article_id, tag_id, created_at)
+         SELECT article_id, ?, created_at FROM article_tags WHERE tag_id = ?",
+        params![target_id, source_id],
+    )
+    .map_err(|e| e.to_string())?;
+    db.execute("DELETE FROM article_tags WHERE tag_id = ?", [source_id])
+        .map_err(|e| e.to_string())?;
+
+    db.execute(
+        "UPDATE tags SET parent_id = ? WHERE parent_id = ?",
+        params![target_id, source_id],
+    )
+    .map_err(|e| e.to_string())?;
+    db.execute(
+        "UPDATE tag_aliases SET tag_id = ? WHERE tag_id = ?",
+        params![target_id, source_id],
+    )
+    .map_err(|e| e.to_string())?;
+    db.execute("DELETE FROM tags WHERE id = ?", [source_id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// タグ階層をツリーで取得（ゴミ箱内の記事は数えない）
+pub fn tag_tree(db: &Connection) -> Result<Vec<TagNode>, String> {
+    let mut stmt = db
+        .prepare("SELECT id, TRIM(name), parent_id FROM tags ORDER BY name")
+        .map_err(|e| e.to_string())?;
+    let tags = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<i64>>(2)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut stmt = db
+        .prepare(
+            "SELECT at.tag_id, at.article_id
+            FROM article_tags at
+            JOIN articles a
+              ON at.article_id = a.id
+            WHERE a.deleted_at IS NULL",
+        )
+        .map_err(|e| e.to_string())?;
+    let mut articles: HashMap<i64, HashSet<i64>> = HashMap::new();
+    let links = stmt
+        .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))
+        .map_err(|e| e.to_string())?;
+    for link in links {
+        let (tag_id, article_id) = link.map_err(|e| e.to_string())?;
+        articles.entry(tag_id).or_default().insert(article_id);
+    }
+
+    let names: HashMap<i64, String> = tags
+        .iter()
+        .map(|(id, name, _)| (*id, name.clone()))
+        .collect();
+    let mut children: HashMap<i64, Vec<i64>> = HashMap::new();
+    let mut roots = Vec::new();
+    for (id, _, parent_id) in &tags {
+        match parent_id.filter(|parent_id| names.contains_key(parent_id)) {
+            Some(parent_id) => children.entry(parent_id).or_default().push(*id),
+            None => roots.push(*id),
+        }
+    }
+
+    let tree = TreeSource {
+        names: &names,
+        children: &children,
+        articles: &articles,
+    };
+    Ok(roots.into_iter().map(|id| tree.build(id).0).collect())
+}
+
+/// 指定タグとその子孫タグのID
+pub fn subtree_ids(db: &Connection, tag: &str) -> Result<HashSet<i64>, String> {
+    subtree_of(db, get_tag_id(db, tag)?)
+}
+
+fn subtree_of(db: &Connection, tag_id: i64) -> Result<HashSet<i64>, String> {
+    let mut stmt = db
+        .prepare(
+            "WITH RECURSIVE subtree(id) AS (
+                SELECT ?
+                UNION
+                SELECT c.id FROM tags c JOIN subtree ON c.parent_id = subtree.id
+            )
+            SELECT id FROM subtree",
+        )
+        .map_err(|e| e.to_string())?;
+    let ids = stmt
+        .query_map([tag_id], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    ids.collect::<Result<HashSet<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+// ツリー組み立て用の材料
+struct TreeSource<'a> {
+    names: &'a HashMap<i64, String>,
+    children: &'a HashMap<i64, Vec<i64>>,
+    articles: &'a HashMap<i64, HashSet<i64>>,
+}
+
+impl TreeSource<'_> {
+    // ノードと、子孫を含めた記事IDの集合を返す
+    fn build(&self, id: i64) -> (TagNode, HashSet<i64>) {
+        let mut all_articles = self.articles.get(&id).cloned().unwrap_or_default();
+        let article_count = count(&all_articles);
+
+        let mut children = Vec::new();
+        for &child_id in self.children.get(&id).into_iter().flatten() {
+            let (child, child_articles) = self.build(child_id);
+            all_articles.extend(child_articles);
+            children.push(child);
+        }
+
+        let node = TagNode {
+            id,
+            name: self.names[&id].clone(),
+            article_count,
+            total_count: count(&all_articles),
+            children,
+        };
+        (node, all_articles)
+    }
+}
+
+fn count(articles: &HashSet<i64>) -> u32 {
+    u32::try_from(articles.len()).unwrap_or(u32::MAX)
+}
+
+fn get_tag_id(db: &Connection, name: &str) -> Result<i64, String> {
+    find_tag_id(db, name)?.ok_or_else(|| format!("タグ「{}」が見つかりません", name.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{migration, repository};
+
+    fn test_db() -> Connection {
+        let mut db = Connection::open_in_memory().unwrap();
+        migration::migrate(&mut db).unwrap();
+        db.execute_batch(
+            "INSERT INTO tags (name) VALUES ('programming'), ('rust'), ('python'), ('tokio');
+             INSERT INTO articles (url, title) VALUES ('https://example.com/1', 'one');
+             INSERT INTO articles (url, title) VALUES ('https://example.com/2', 'two');
+             INSERT INTO article_tags (article_id, tag_id) VALUES (1, 2), (1, 4), (2, 3);",
+        )
+        .unwrap();
+        db
+    }
+
+    #[test]
+    fn test_tag_tree_counts() {
+        let db = test_db();
+        set_tag_parent(&db, "rust", Some("programming")).unwrap();
+        set_tag_parent(&db, "Python", Some("programming")).unwrap();
+        set_tag_parent(&db, "tokio", Some("rust")).unwrap();
+
+        let tree = tag_tree(&db).unwrap();
+        assert_eq!(tree.len(), 1);
+        let programming = &tree[0];
+        assert_eq!(programming.name, "programming");
+        assert_eq!(programming.article_count, 0);
+        assert_eq!(programming.total_count, 2);
+
+        let rust = &programming.children[1];
+        assert_eq!(rust.name, "rust");
+        assert_eq!((rust.article_count, rust.total_count), (1, 1));
+        assert_eq!(rust.children[0].name, "tokio");
+    }
+
+    #[test]
+    fn test_move_subtree_and_prevent_cycles() {
+        let db = test_db();
+        set_tag_parent(&db, "rust", Some("programming")).unwrap();
+        set_tag_parent(&db, "tokio", Some("rust")).unwrap();
+
+        // 自分自身・子孫の下へは移動できない
+        assert!(set_tag_parent(&db, "programming", Some("tokio")).is_err());
+        assert!(set_tag_parent(&db, "rust", Some("rust")).is_err());
+        assert!(set_tag_parent(&db, "rust", Some("no-such-tag")).is_err());
+
+        // 親を付け替えると子孫もついてくる
+        set_tag_parent(&db, "rust", Some("python")).unwrap();
+        assert_eq!(subtree_ids(&db, "python").unwrap().len(), 3);
+        assert_eq!(subtree_ids(&db, "programming").unwrap().len(), 1);
+
+        set_tag_parent(&db, "rust", None).unwrap();
+        assert_eq!(tag_tree(&db).unwrap().len(), 3);
+    }
+
+    fn tags_of(db: &Connection, article_id: i64) -> Vec<String> {
+        let mut stmt = db
+            .prepare(
+                "SELECT t.name FROM article_tags at JOIN tags t ON at.tag_id = t.id
+                 WHERE at.article_id = ? ORDER BY t.name",
+            )
+            .unwrap();
+        stmt.query_map([article_id], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_rename_tag() {
+        let mut db = test_db();
+        db.execute("INSERT INTO tags (name) VALUES ('javscript')", [])
+            .unwrap();
+
+        rename_tag(&db, "javscript", " JavaScript ").unwrap();
+        let name: String = db
+            .query_row("SELECT name FROM tags WHERE id = 5", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(name, "javascript");
+
+        assert!(rename_tag(&db, "rust", "  ").is_err());
+
+        // 既存タグと同じ名前にすると統合され、両方付いていた記事は1件にまとまる
+        db.execute(
+            "INSERT INTO article_tags (article_id, tag_id) VALUES (1, 3)",
+            [],
+        )
+        .unwrap();
+        repository::write(&mut db, |db| rename_tag(db, "rust", "Python")).unwrap();
+        assert_eq!(tags_of(&db, 1), ["python", "tokio"]);
+        assert_eq!(tags_of(&db, 2), ["python"]);
+        assert_eq!(find_tag_id(&db, "rust").unwrap(), Some(3));
+    }
+
+    #[test]
+    fn test_merge_tags_deduplicates() {
+        let mut db = test_db();
+        db.execute_batch(
+            "INSERT INTO tags (name) VALUES ('rustlang');
+             INSERT INTO article_tags (article_id, tag_id) VALUES (1, 5), (2, 5);",
+        )
+        .unwrap();
+        set_tag_parent(&db, "tokio", Some("rustlang")).unwrap();
+
+        merge_tags(&db, &["rustlang".into()], "rust").unwrap();
+
+        // 記事1は両方付いていたので1件にまとまる
+        assert_eq!(tags_of(&db, 1), ["rust", "tokio"]);
+        assert_eq!(tags_of(&db, 2), ["python", "rust"]);
+        assert!(subtree_ids(&db, "rust").unwrap().contains(&4));
+        // 統合元の名前は別名として残る
+        assert_eq!(canonical_name(&db, "rustlang").unwrap(), "rust");
+
+        // 存在しないタグが含まれていれば何も変わらない
+        assert!(repository::write(&mut db, |db| {
+            merge_tags(db, &["python".into(), "nope".into()], "rust")
+        })
+        .is_err());
+        assert_eq!(tags_of(&db, 2), ["python", "rust"]);
+    }
+
+    #[test]
+    fn test_normalize_tag_name() {
+        assert_eq!(normalize_tag_name("  JavaScript "), "javascript");
+        assert_eq!(normalize_tag_name("ＪａｖａＳｃｒｉｐｔ"), "javascript");
+        assert_eq!(normalize_tag_name("Machine　 Learning"), "machine learning");
+        assert_eq!(normalize_tag_name("ﾌﾟﾛｸﾞﾗﾐﾝｸﾞ"), "プログラミング");
+        assert_eq!(normalize_tag_name(" \u{3000} "), "");
+    }
+
+    #[test]
+    fn test_normalize_existing_tags_and_aliases() {
+        let db = test_db();
+        db.execute_batch(
+            "INSERT INTO tags (name) VALUES ('JavaScript'), ('javascript '), ('js');
+             INSERT INTO article_tags (article_id, tag_id) VALUES (1, 5), (1, 6), (2, 6);",
+        )
+        .unwrap();
+
+        assert_eq!(normalize_existing_tags(&db).unwrap(), 2);
+        assert_eq!(normalize_existing_tags(&db).unwrap(), 0);
+        assert_eq!(tags_of(&db, 1), ["javascript", "rust", "tokio"]);
+        assert_eq!(tags_of(&db, 2), ["javascript", "python"]);
+
+        // 既存のタグ名は別名にできない
+        assert!(add_alias(&db, "JS", "javascript").is_err());
+        merge_tags(&db, &["js".into()], "javascript").unwrap();
+        add_alias(&db, "ＥＣＭＡＳｃｒｉｐｔ", "javascript").unwrap();
+
+        let javascript = find_tag_id(&db, "javascript").unwrap();
+        assert_eq!(find_tag_id(&db, "ecmascript").unwrap(), javascript);
+        let aliases: Vec<String> = list_aliases(&db)
+            .unwrap()
+            .into_iter()
+            .map(|alias| alias.alias)
+            .collect();
+        assert_eq!(aliases, ["ecmascript", "js"]);
+
+        remove_alias(&db, "js").unwrap();
+        assert!(remove_alias(&db, "js").is_err());
+        assert_eq!(canonical_name(&db, "JS").unwrap(), "js");
+    }
+}
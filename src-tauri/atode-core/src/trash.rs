@@ -0,0 +1,173 @@
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::articles::timestamp_from_row;
+
+/// ゴミ箱内の記事
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrashedArticle {
+    pub id: i64,
+    pub url: String,
+    pub title: String,
+    pub site_name: Option<String>,
+    // RFC 3339（UTC）
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// 記事をゴミ箱へ移す（タグやメモはそのまま残る）
+pub fn move_to_trash(db: &Connection, article_id: i64) -> Result<(), String> {
+    let rows = db
+        .execute(
+            "UPDATE articles SET deleted_at = CURRENT_TIMESTAMP
+             WHERE id = ? AND deleted_at IS NULL",
+            [article_id],
+        )
+        .map_err(|e| e.to_string())?;
+    if rows == 0 {
+        return Err(format!("記事が見つかりません (ID: {article_id})"));
+    }
+
+    log!("🗑️ ゴミ箱へ移動: ID {article_id}");
+    Ok(())
+}
+
+/// ゴミ箱内の記事一覧（削除が新しい順）
+pub fn list_trash(db: &Connection) -> Result<Vec<TrashedArticle>, String> {
+    let mut stmt = db
+        .prepare(
+            "SELECT
+               a.id
+             , a.url
+             , a.title
+             , s.name as site_name
+             , a.deleted_at
+            FROM articles a
+            LEFT JOIN sites s
+              ON a.site_id = s.id
+            WHERE a.deleted_at IS NOT NULL
+            ORDER BY a.deleted_at DESC, a.id DESC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let articles = stmt
+        .query_map([], |row| {
+            Ok(TrashedArticle {
+                id: row.get(0)?,
+                url: row.get(1)?,
+                title: row.get(2)?,
+                site_name: row.get(3)?,
+                deleted_at: timestamp_from_row(4, &row.get::<_, String>(4)?)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    articles
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+/// ゴミ箱から記事を元に戻す
+pub fn restore_article(db: &Connection, article_id: i64) -> Result<(), String> {
+    let rows = db
+        .execute(
+            "UPDATE articles SET deleted_at = NULL WHERE id = ? AND deleted_at IS NOT NULL",
+            [article_id],
+        )
+        .map_err(|e| e.to_string())?;
+    if rows == 0 {
+        return Err(format!("ゴミ箱に記事が見つかりません (ID: {article_id})"));
+    }
+
+    log!("♻️ ゴミ箱から復元: ID {article_id}");
+    Ok(())
+}
+
+/// ゴミ箱を空にする（タグとの関連も外部キーで削除される）。削除件数を返す
+pub fn empty_trash(db: &Connection) -> Result<usize, String> {
+    let rows = db
+        .execute("DELETE FROM articles WHERE deleted_at IS NOT NULL", [])
+        .map_err(|e| e.to_string())?;
+
+    log!("🧹 ゴミ箱を空にしました: {rows}件");
+    Ok(rows)
+}
+
+/// ゴミ箱へ移してから`days`日以上経った記事を完全削除する。削除件数を返す
+pub fn purge_expired(db: &Connection, days: u32) -> Result<usize, String> {
+    let rows = db
+        .execute(
+            "DELETE FROM articles
+             WHERE deleted_at IS NOT NULL
+               AND deleted_at <= datetime('now', ?)",
+            params![format!("-{days} days")],
+        )
+        .map_err(|e| e.to_string())?;
+
+    if rows > 0 {
+        log!("🧹 保存期間({days}日)を過ぎたゴミ箱の記事を削除: {rows}件");
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::migration;
+
+    fn test_db() -> Connection {
+        let mut db = Connection::open_in_memory().unwrap();
+        migration::migrate(&mut db).unwrap();
+        db.execute_batch(
+            "PRAGMA foreign_keys = ON;
+             INSERT INTO articles (url, title) VALUES ('https://example.com/a', 'A');
+             INSERT INTO articles (url, title) VALUES ('https://example.com/b', 'B');
+             INSERT INTO tags (name) VALUES ('rust');
+             INSERT INTO article_tags (article_id, tag_id) VALUES (1, 1);",
+        )
+        .unwrap();
+        db
+    }
+
+    fn count(db: &Connection, sql: &str) -> i64 {
+        db.query_row(sql, [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn test_trash_and_restore_keeps_tags() {
+        let db = test_db();
+        move_to_trash(&db, 1).unwrap();
+        // 既にゴミ箱にある記事は再度移せない
+        assert!(move_to_trash(&db, 1).is_err());
+
+        let trash = list_trash(&db).unwrap();
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].title, "A");
+        assert_eq!(count(&db, "SELECT COUNT(*) FROM article_tags"), 1);
+
+        restore_article(&db, 1).unwrap();
+        assert!(list_trash(&db).unwrap().is_empty());
+        assert!(restore_article(&db, 1).is_err());
+        assert_eq!(count(&db, "SELECT COUNT(*) FROM article_tags"), 1);
+    }
+
+    #[test]
+    fn test_empty_and_purge() {
+        let db = test_db();
+        move_to_trash(&db, 1).unwrap();
+        move_to_trash(&db, 2).unwrap();
+        db.execute(
+            "UPDATE articles SET deleted_at = datetime('now', '-40 days') WHERE id = 1",
+            [],
+        )
+        .unwrap();
+
+        // 保存期間を過ぎた記事だけが消える
+        assert_eq!(purge_expired(&db, 30).unwrap(), 1);
+        assert_eq!(count(&db, "SELECT COUNT(*) FROM article_tags"), 0);
+        assert_eq!(list_trash(&db).unwrap()[0].title, "B");
+
+        assert_eq!(empty_trash(&db).unwrap(), 1);
+        assert_eq!(count(&db, "SELECT COUNT(*) FROM articles"), 0);
+    }
+}
@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::sites;
+
+/// URL正規化のルール（config.json の `url_normalization`）
+///
+/// 既定ではクエリパラメータをすべて除く。ホストに一致するサイトのルールがあれば
+/// `keep_params`のパラメータだけ残す。`strip_params`（トラッキング用）は常に除く
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UrlRules {
+    /// 全サイト共通で除くパラメータ（末尾の`*`で前方一致。例: `utm_*`）
+    pub strip_params: Vec<String>,
+    /// フラグメント（`#`以降）を残すか
+    pub keep_fragment: bool,
+    /// サイトごとのルール（先に書いたものを優先）
+    pub sites: Vec<SiteUrlRule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SiteUrlRule {
+    /// ホスト名（サブドメインも一致。youtube.com → www.youtube.com）
+    pub host: Vec<String>,
+    /// 残すパラメータ（末尾の`*`で前方一致。`*`だけならすべて）
+    #[serde(default)]
+    pub keep_params: Vec<String>,
+    /// フラグメントを残すか（省略時は全体の設定）
+    #[serde(default)]
+    pub keep_fragment: Option<bool>,
+}
+
+impl Default for UrlRules {
+    fn default() -> Self {
+        let strings = |values: &[&str]| values.iter().map(ToString::to_string).collect();
+        Self {
+            strip_params: strings(&["utm_*", "fbclid", "gclid"]),
+            keep_fragment: false,
+            sites: vec![
+                SiteUrlRule {
+                    host: strings(&["youtube.com", "youtu.be"]),
+                    keep_params: strings(&["v", "list", "t"]),
+                    keep_fragment: None,
+                },
+                SiteUrlRule {
+                    host: strings(&["news.ycombinator.com"]),
+                    keep_params: strings(&["id"]),
+                    keep_fragment: None,
+                },
+            ],
+        }
+    }
+}
+
+impl UrlRules {
+    /// URLを正規化する（ローカルファイルや解析できないURLはそのまま）
+    pub fn normalize(&self, url: &str) -> String {
+        if url.starts_with("file://") {
+            return url.to_string();
+        }
+        let Ok(mut parsed) = Url::parse(url) else {
+            return url.to_string();
+        };
+
+        let host = sites::normalize_host(parsed.host_str().unwrap_or(""));
+        let site = self.sites.iter().find(|site| {
+            site.host.iter().any(|pattern| {
+                let pattern = sites::normalize_host(pattern);
+                host == pattern || host.ends_with(&format!(".{pattern}"))
+            })
+        });
+
+        // 元のエンコードのまま残すため、パラメータは分解し直さない
+        let kept: Vec<&str> = parsed
+            .query()
+            .unwrap_or("")
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .filter(|pair| {
+                let key = pair.split_once('=').map_or(*pair, |(key, _)| key);
+                site.is_some_and(|site| matches_any(&site.keep_params, key))
+                    && !matches_any(&self.strip_params, key)
+            })
+            .collect();
+        let query = (!kept.is_empty()).then(|| kept.join("&"));
+
+        let keep_fragment = site
+            .and_then(|site| site.keep_fragment)
+            .unwrap_or(self.keep_fragment);
+
+        parsed.set_query(query.as_deref());
+        if !keep_fragment {
+            parsed.set_fragment(None);
+        }
+        parsed.to_string()
+    }
+}
+
+fn matches_any(patterns: &[String], key: &str) -> bool {
+    patterns.iter().any(|pattern| {
+        pattern.strip_suffix('*').map_or_else(
+            || pattern.eq_ignore_ascii_case(key),
+            |prefix| {
+                key.get(..prefix.len())
+                    .is_some_and(|head| head.eq_ignore_ascii_case(prefix))
+            },
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_rules() {
+        let rules = UrlRules::default();
+        let cases = [
+            // 通常はクエリとフラグメントを除く
+            (
+                "https://example.com/page?ref=123",
+                "https://example.com/page",
+            ),
+            (
+                "https://example.com/page#section",
+                "https://example.com/page",
+            ),
+            (
+                "https://github.com/user/repo",
+                "https://github.com/user/repo",
+            ),
+            // 残すパラメータだけ残す（順序・エンコードはそのまま）
+            (
+                "https://www.youtube.com/watch?v=a1b2&utm_source=x&t=30s&feature=share",
+                "https://www.youtube.com/watch?v=a1b2&t=30s",
+            ),
+            (
+                "https://youtu.be/a1b2?si=abc&t=10",
+                "https://youtu.be/a1b2?t=10",
+            ),
+            (
+                "https://news.ycombinator.com/item?id=42&p=2",
+                "https://news.ycombinator.com/item?id=42",
+            ),
+            (
+                "https://www.youtube.com/?feature=ytca",
+                "https://www.youtube.com/",
+            ),
+            // 解析できないURL・ローカルファイルはそのまま
+            ("not a url", "not a url"),
+            (
+                "file:///C:/Users/test/document.html?x=1",
+                "file:///C:/Users/test/document.html?x=1",
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(rules.normalize(input), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_configured_rules() {
+        let rules: UrlRules = serde_json::from_str(
+            r#"{
+                "strip_params": ["utm_*", "FBCLID"],
+                "keep_fragment": true,
+                "sites": [
+                    {"host": ["docs.example.com"], "keep_params": ["*"], "keep_fragment": false},
+                    {"host": ["example.com"], "keep_params": ["q", "page*"]}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let cases = [
+            // 先に書いたルールが優先。`*`でもトラッキング用は除く
+            (
+                "https://docs.example.com/a?x=1&fbclid=2&utm_medium=3#top",
+                "https://docs.example.com/a?x=1",
+            ),
+            (
+                "https://www.example.com/s?q=rust%20lang&page_size=10&sort=new#results",
+                "https://www.example.com/s?q=rust%20lang&page_size=10#results",
+            ),
+            // サイトのルールが無ければクエリを除き、フラグメントは全体の設定に従う
+            ("https://other.org/a?q=1#top", "https://other.org/a#top"),
+            // ホストはサブドメインのみ一致（notexample.com は別サイト）
+            ("https://notexample.com/s?q=1", "https://notexample.com/s"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(rules.normalize(input), expected, "{input}");
+        }
+    }
+}
@@ -0,0 +1,60 @@
+-- v1.1 時点のアプリが作成したデータベースの再現（schema_version テーブルなし）
+CREATE TABLE sites(
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT UNIQUE NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE articles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    url TEXT UNIQUE NOT NULL,
+    title TEXT NOT NULL,
+    site_id INTEGER,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (site_id) REFERENCES sites(id)
+);
+
+CREATE TABLE tags (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT UNIQUE NOT NULL,
+    parent_id INTEGER,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (parent_id) REFERENCES tags(id) ON DELETE SET NULL
+);
+
+CREATE TABLE article_tags(
+    article_id INTEGER,
+    tag_id INTEGER,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (article_id, tag_id),
+    FOREIGN KEY (article_id) REFERENCES articles(id) ON DELETE CASCADE,
+    FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+);
+
+INSERT INTO sites (id, name, created_at) VALUES
+    (1, 'github.com', '2025-06-01 09:00:00'),
+    (2, 'qiita.com', '2025-06-02 10:30:00'),
+    (3, 'youtube.com', '2025-06-03 21:15:00');
+
+INSERT INTO articles (id, url, title, site_id, created_at, updated_at) VALUES
+    (1, 'https://github.com/rust-lang/rust', 'rust-lang/rust: Empowering everyone', 1, '2025-06-01 09:00:00', '2025-06-01 09:00:00'),
+    (2, 'https://qiita.com/someone/items/0123456789abcdef', 'Rustの所有権を図解で理解する', 2, '2025-06-02 10:30:00', '2025-06-05 08:00:00'),
+    (3, 'https://www.youtube.com/watch?v=a1b2c3d4e5', 'Tauri 2.0 入門', 3, '2025-06-03 21:15:00', '2025-06-03 21:15:00');
+
+INSERT INTO tags (id, name, parent_id, created_at) VALUES
+    (1, 'github', NULL, '2025-06-01 09:00:00'),
+    (2, 'programming', NULL, '2025-06-01 09:00:00'),
+    (3, 'qiita', NULL, '2025-06-02 10:30:00'),
+    (4, 'rust', NULL, '2025-06-02 10:30:00'),
+    (5, 'youtube', NULL, '2025-06-03 21:15:00'),
+    (6, 'video', NULL, '2025-06-03 21:15:00');
+
+INSERT INTO article_tags (article_id, tag_id, created_at) VALUES
+    (1, 1, '2025-06-01 09:00:00'),
+    (1, 2, '2025-06-01 09:00:00'),
+    (2, 2, '2025-06-02 10:30:00'),
+    (2, 3, '2025-06-02 10:30:00'),
+    (2, 4, '2025-06-05 08:00:00'),
+    (3, 5, '2025-06-03 21:15:00'),
+    (3, 6, '2025-06-03 21:15:00');
@@ -1,17 +1,13 @@
 //================================================================================================
 // 依存関係 - Import Section
 //================================================================================================
-use regex::Regex;
-use rusqlite::{params, Connection, OptionalExtension, Result};
-use serde::{Deserialize, Serialize};
-use std::fs;
 use std::path::PathBuf;
 use std::sync::{
-    atomic::{AtomicBool, AtomicU64, Ordering},
-    Mutex,
+    atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering},
+    Mutex, RwLock,
 };
 use tauri::{
-    menu::{MenuBuilder, MenuItem},
+    menu::{Menu, MenuBuilder, MenuItem, SubmenuBuilder},
     // Tauri 2.0 imports
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
     AppHandle,
@@ -24,7 +20,6 @@ use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
 // Win32 APIホットキー実装用
 #[cfg(target_os = "windows")]
 use std::{mem, ptr, thread};
-use url::Url;
 #[cfg(target_os = "windows")]
 use winapi::um::winuser::{
     DispatchMessageW, GetMessageW, PostQuitMessage, RegisterHotKey, TranslateMessage,
@@ -35,6 +30,23 @@ use winapi::um::winuser::{
 mod browser_info_bridge;
 use browser_info_bridge::get_active_browser_info;
 
+// 記事管理のロジック（保存・検索・タグ・サイト・ステータスなど）
+use atode_core::auto_tag::{self, auto_tag_list, auto_tagging, AutoTagPreview, TagRules};
+use atode_core::canonical::{self, DuplicateGroup};
+use atode_core::config::{self, Config};
+use atode_core::resave::SaveResult;
+use atode_core::saved_searches::{self, SavedSearch};
+use atode_core::sites::{self, SiteAlias, SiteSummary};
+use atode_core::statistics::{self, Statistics, StatisticsOptions};
+use atode_core::status::{self, Status, StatusChange};
+use atode_core::tag_graph::{self, RelatedTag, TagGraph};
+use atode_core::tags::{self, TagAlias, TagNode};
+use atode_core::trash::{self, TrashedArticle};
+use atode_core::{
+    notes, ArticlePage, ArticleStore, SaveArticleRequest, SearchFilters, SqliteStore, TagCount,
+    UpdateResult,
+};
+
 //================================================================================================
 // データ構造・モジュール変数等 - Data Types & Module Variables
 //================================================================================================
@@ -42,6 +54,13 @@ use browser_info_bridge::get_active_browser_info;
 // グローバルなリフレッシュフラグ
 static REFRESH_NEEDED: AtomicBool = AtomicBool::new(false);
 
+// トレイメニューで選ばれた保存済み検索のID（0なら無し）。フロントエンドが取り出して適用する
+static PENDING_SAVED_SEARCH: AtomicI64 = AtomicI64::new(0);
+
+// システムトレイのID（保存した検索が変わったらメニューを作り直す）
+const TRAY_ID: &str = "main";
+const SAVED_SEARCH_MENU_PREFIX: &str = "saved_search:";
+
 // ホットキーデバウンス用のタイムスタンプ（ミリ秒）
 static LAST_SAVE_HOTKEY: AtomicU64 = AtomicU64::new(0);
 static LAST_TOGGLE_HOTKEY: AtomicU64 = AtomicU64::new(0);
@@ -70,98 +89,48 @@ const VK_A: u32 = 0x41;
 // デバウンス間隔（ミリ秒）
 const DEBOUNCE_MS: u64 = 500;
 
-// 設定ファイル構造体
-#[derive(Debug, Serialize, Deserialize)]
-struct Config {
-    database_path: String,
-}
-
-// main
-#[derive(Debug, Serialize, Deserialize)]
-struct Article {
-    id: i64,
-    url: String,
-    title: String,
-    site_id: Option<i64>,
-    created_at: String,
-    updated_at: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct Site {
-    id: i64,
-    name: String,
-    created_at: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct Tag {
-    id: i64,
-    name: String,
-    parent_id: Option<i64>,
-    created_at: String,
-}
-
-// フロントエンド用（結果）
-#[derive(Debug, Serialize, Deserialize)]
-struct ArticleWithDetails {
-    id: i64,
-    url: String,
-    title: String,
-    site_name: Option<String>,
-    tags: Vec<String>,
-    created_at: String,
-    updated_at: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct SearchFilters {
-    tag_query: Option<String>,
-    site: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct SaveArticleRequest {
-    url: String,
-    title: String,
-    tags: Option<String>,
-}
-
-// "よく使う"タグ管理用
-#[derive(Debug, Serialize, Deserialize)]
-struct TagCount {
-    tag: String,
-    count: u32,
-}
-
 struct AppState {
-    db: Mutex<Connection>,
+    // 記事データベース（URL正規化・再保存のルールを含む）
+    store: Mutex<SqliteStore>,
+    // 自動タグ付けのルール（reload_tag_rules で読み直せる）
+    tag_rules: RwLock<TagRules>,
+    tag_rules_path: PathBuf,
 }
 
-// 自動タグ付け用正規表現
-// 正規表現を一度だけコンパイル（プログラム起動時）
-static PREFIX_RE: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| {
-    Regex::new(r"^(www\.|m\.|mobile\.|app\.|beta\.|dev\.|staging\.|blog\.|news\.|support\.|help\.|doc\.|api\.|cdn\.|static\.|shop\.|jp\.)").unwrap()
-});
-
-static COMPOUND_TLD_RE: std::sync::LazyLock<Regex> =
-    std::sync::LazyLock::new(|| Regex::new(r"\.(co|com|ac|or|ne|go|ed|gov)\.[a-z]{2,3}$").unwrap());
-
-static TLD_RE: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| {
-    Regex::new(r"\.(com|net|org|edu|gov|mil|int|info|biz|name|pro|jp|us|uk|de|fr|ca|au|cn|kr|in|br|ru|it|es|io|ai|co|me|tv|cc|ly|app|dev|tech|blog|news|shop)$").unwrap()
-});
-
 //================================================================================================
 // メイン処理 - Main Procedure
 //================================================================================================
 
 fn main() {
     let config = load_config();
-    let db = init_database(&config.database_path).expect("DB初期化失敗");
+    let mut store = SqliteStore::open(
+        &config.database_path,
+        config.url_normalization,
+        config.resave,
+    )
+    .expect("DB初期化失敗");
+
+    // 保存期間を過ぎたゴミ箱の記事を完全削除
+    if config.trash_retention_days > 0 {
+        if let Err(e) = store.write(|db| trash::purge_expired(db, config.trash_retention_days)) {
+            eprintln!("⚠️ ゴミ箱の自動削除に失敗: {e}");
+        }
+    }
+
+    // ルールに誤りがあれば既定のルールで起動する（直してから reload_tag_rules で読み直せる）
+    let tag_rules_path = PathBuf::from(&config.tag_rules_path);
+    let tag_rules = TagRules::load(&tag_rules_path).unwrap_or_else(|e| {
+        eprintln!("⚠️ {e} - 既定のルールを使用");
+        TagRules::parse(auto_tag::DEFAULT_RULES).unwrap_or_default()
+    });
 
     tauri::Builder::default()
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
-        .manage(AppState { db: Mutex::new(db) })
+        .manage(AppState {
+            store: Mutex::new(store),
+            tag_rules: RwLock::new(tag_rules),
+            tag_rules_path,
+        })
         .invoke_handler(tauri::generate_handler![
             // 記事管理
             get_articles,
@@ -174,6 +143,53 @@ fn main() {
             check_refresh_needed,
             // UX強化用
             get_popular_tags,
+            get_statistics,
+            preview_auto_tags,
+            reload_tag_rules,
+            rebuild_search_index,
+            // 読書ステータス
+            get_statuses,
+            create_status,
+            rename_status,
+            delete_status,
+            reorder_statuses,
+            set_article_status,
+            get_article_status_history,
+            // メモ
+            get_article_notes,
+            set_article_notes,
+            // ゴミ箱
+            get_trash,
+            restore_article,
+            empty_trash,
+            // 重複記事
+            find_duplicate_articles,
+            merge_duplicate_articles,
+            // タグ階層
+            get_tag_tree,
+            set_tag_parent,
+            // 関連タグ
+            get_related_tags,
+            get_tag_graph,
+            // タグ管理
+            rename_tag,
+            merge_tags,
+            get_tag_aliases,
+            add_tag_alias,
+            remove_tag_alias,
+            // サイト管理
+            get_sites,
+            rename_site,
+            merge_sites,
+            get_site_aliases,
+            add_site_alias,
+            remove_site_alias,
+            // 保存した検索
+            get_saved_searches,
+            create_saved_search,
+            update_saved_search,
+            delete_saved_search,
+            take_pending_saved_search,
         ])
         .setup(setup_application)
         .on_window_event(handle_window_event)
@@ -546,6 +562,58 @@ fn handle_window_event(window: &tauri::Window<tauri::Wry>, event: &tauri::Window
 fn create_system_tray(
     app_handle: &AppHandle<tauri::Wry>,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    let menu = build_tray_menu(app_handle)?;
+
+    let app_handle_clone = app_handle.clone();
+    let _tray = TrayIconBuilder::with_id(TRAY_ID)
+        .menu(&menu)
+        .icon(app_handle.default_window_icon().unwrap().clone())
+        .on_menu_event(move |_app, event| {
+            handle_system_tray_menu_event(&app_handle_clone, &event);
+        })
+        .on_tray_icon_event(move |tray, event| {
+            handle_system_tray_click_event(tray, &event);
+        })
+        .build(app_handle)?;
+
+    Ok(())
+}
+
+fn build_tray_menu(
+    app_handle: &AppHandle<tauri::Wry>,
+) -> Result<Menu<tauri::Wry>, Box<dyn std::error::Error>> {
+    // 保存した検索（選ぶと絞り込んだ状態でウィンドウを開く）
+    let saved = {
+        let state = app_handle.state::<AppState>();
+        let store = state.store.lock().map_err(|e| e.to_string())?;
+        saved_searches::list_saved_searches(store.connection())?
+    };
+    let saved_items = saved
+        .iter()
+        .map(|search| {
+            MenuItem::with_id(
+                app_handle,
+                format!("{SAVED_SEARCH_MENU_PREFIX}{}", search.id),
+                &search.name,
+                true,
+                None::<&str>,
+            )
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let saved_menu = if saved_items.is_empty() {
+        SubmenuBuilder::new(app_handle, "🔖 保存した検索（なし）")
+            .enabled(false)
+            .build()?
+    } else {
+        saved_items
+            .iter()
+            .fold(
+                SubmenuBuilder::new(app_handle, "🔖 保存した検索"),
+                |builder, item| builder.item(item),
+            )
+            .build()?
+    };
+
     let menu = MenuBuilder::new(app_handle)
         .item(&MenuItem::with_id(
             app_handle,
@@ -554,6 +622,7 @@ fn create_system_tray(
             true,
             None::<&str>,
         )?)
+        .item(&saved_menu)
         .separator()
         .item(&MenuItem::with_id(
             app_handle,
@@ -572,19 +641,22 @@ fn create_system_tray(
         )?)
         .build()?;
 
-    let app_handle_clone = app_handle.clone();
-    let _tray = TrayIconBuilder::new()
-        .menu(&menu)
-        .icon(app_handle.default_window_icon().unwrap().clone())
-        .on_menu_event(move |_app, event| {
-            handle_system_tray_menu_event(&app_handle_clone, &event);
-        })
-        .on_tray_icon_event(move |tray, event| {
-            handle_system_tray_click_event(tray, &event);
-        })
-        .build(app_handle)?;
+    Ok(menu)
+}
 
-    Ok(())
+// 保存した検索の変更をトレイメニューに反映
+fn refresh_tray_menu(app_handle: &AppHandle<tauri::Wry>) {
+    let Some(tray) = app_handle.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    match build_tray_menu(app_handle) {
+        Ok(menu) => {
+            if let Err(e) = tray.set_menu(Some(menu)) {
+                eprintln!("⚠️ トレイメニューの更新に失敗: {e}");
+            }
+        }
+        Err(e) => eprintln!("⚠️ トレイメニューの作成に失敗: {e}"),
+    }
 }
 
 // システムトレイメニューイベントの処理
@@ -622,7 +694,20 @@ fn handle_system_tray_menu_event(app: &AppHandle<tauri::Wry>, event: &tauri::men
             cleanup_on_exit(app);
             std::process::exit(0);
         }
-        _ => {}
+        id => {
+            // 保存した検索：フロントエンドが次のポーリングで取り出して絞り込む
+            if let Some(Ok(search_id)) = id
+                .strip_prefix(SAVED_SEARCH_MENU_PREFIX)
+                .map(str::parse::<i64>)
+            {
+                PENDING_SAVED_SEARCH.store(search_id, Ordering::Relaxed);
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+                println!("システムトレイ: 保存した検索を開く (ID: {search_id})");
+            }
+        }
     }
 }
 
@@ -679,264 +764,56 @@ fn check_refresh_needed() -> bool {
 fn get_articles(
     state: State<AppState>,
     filters: Option<SearchFilters>,
-) -> Result<Vec<ArticleWithDetails>, String> {
+) -> Result<ArticlePage, String> {
     // 記事検索
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-
-    let mut query = "SELECT 
-        a.id,
-        a.url,
-        a.title,
-        COALESCE(s.name, '') as site_name,
-        GROUP_CONCAT(t.name) as tags,
-        a.created_at,
-        a.updated_at
-     FROM articles a
-     LEFT JOIN sites s ON a.site_id = s.id
-     LEFT JOIN article_tags at ON a.id = at.article_id
-     LEFT JOIN tags t ON at.tag_id = t.id
-     
-    "
-    .to_string();
-
-    let mut conditions = Vec::new();
-    let mut params: Vec<String> = Vec::new();
-
-    if let Some(filters) = filters {
-        // フィルター処理：サイト
-        if let Some(site) = filters.site {
-            conditions.push("s.name LIKE ?".to_string());
-            params.push(format!("%{site}%"));
-        }
-        // フィルター処理：タグ
-        if let Some(tag_query) = filters.tag_query {
-            let search_tags: Vec<&str> = tag_query.split(',').map(str::trim).collect();
-            for tag in search_tags {
-                conditions.push("t.name = ? COLLATE NOCASE".to_string());
-                params.push(tag.to_string());
-            }
-        }
-    }
-
-    if !conditions.is_empty() {
-        query.push_str(" WHERE ");
-        query.push_str(&conditions.join(" AND "));
-    }
-
-    query.push_str(" GROUP BY a.id, a.url, a.title, s.name, a.created_at, a.updated_at ");
-    query.push_str(" ORDER BY updated_at DESC");
-
-    let mut stmt = db.prepare(&query).map_err(|e| e.to_string())?;
-    let param_refs: Vec<&dyn rusqlite::ToSql> =
-        params.iter().map(|p| p as &dyn rusqlite::ToSql).collect();
-
-    let articles = stmt
-        .query_map(&param_refs[..], |row| {
-            let tags_str: Option<String> = row.get(4)?;
-            let tags = tags_str.map_or_else(Vec::new, |tags_str| {
-                tags_str
-                    .split(',')
-                    .map(|tag| tag.trim().to_string())
-                    .collect()
-            });
-            Ok(ArticleWithDetails {
-                id: row.get(0)?,
-                url: row.get(1)?,
-                title: row.get(2)?,
-                site_name: Some(row.get(3)?),
-                tags,
-                created_at: row.get(5)?,
-                updated_at: row.get(6)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
-
-    let mut result = Vec::new();
-    for article in articles {
-        result.push(article.map_err(|e| e.to_string())?);
-    }
-
-    Ok(result)
+    let store = state.store.lock().map_err(|e| e.to_string())?;
+    store.search_articles(&filters.unwrap_or_default())
 }
 
 #[tauri::command]
 #[allow(clippy::needless_pass_by_value)]
-#[allow(clippy::option_if_let_else)]
-fn save_article(state: State<AppState>, request: SaveArticleRequest) -> Result<String, String> {
+#[allow(clippy::significant_drop_tightening)]
+fn save_article(state: State<AppState>, request: SaveArticleRequest) -> Result<SaveResult, String> {
     println!("記事保存開始: {}", request.url);
 
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-
-    let normalized_url = normalize_url(&request.url);
-    let parsed_url = Url::parse(&normalized_url).map_err(|e| e.to_string())?;
-    let site_name = parsed_url.host_str().unwrap_or("").replace("www.", "");
-
-    // ph.1 サイトID確定
-    let site_id = get_or_create_site(&db, &site_name)?;
-
-    // ph.2 既存記事をチェック
-    let existing_article = db
-        .prepare("SELECT id FROM articles WHERE url = ?")
-        .and_then(|mut stmt| {
-            stmt.query_row([&normalized_url], |row| row.get::<_, i64>(0))
-                .optional()
-        })
-        .map_err(|e| e.to_string())?;
-
-    let (article_id, result_status) = if let Some(existing_id) = existing_article {
-        // 既存記事を更新
-        println!("既存記事を更新: {} (ID: {})", request.title, existing_id);
-        db.execute(
-            "UPDATE articles SET title = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
-            params![request.title, existing_id],
-        )
-        .map_err(|e| e.to_string())?;
-
-        // 既存のタグ関連を削除
-        db.execute(
-            "DELETE FROM article_tags WHERE article_id = ?",
-            params![existing_id],
-        )
-        .map_err(|e| e.to_string())?;
-
-        (existing_id, "updated".to_string())
-    } else {
-        // 新規記事を作成
-        println!("記事保存開始：{}", request.url);
-        db.execute(
-            "INSERT INTO articles (url, title, site_id, created_at, updated_at) 
-             VALUES (?, ?, ?, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)",
-            params![normalized_url, request.title, site_id],
-        )
-        .map_err(|e| e.to_string())?;
-
-        let new_id = db.last_insert_rowid();
-        println!("記事作成完了: {} (ID: {})", request.title, new_id);
-        (new_id, "created".to_string())
-    };
-
-    // ph.3 タグの処理
-    if let Some(tags_str) = request.tags {
-        let tag_names: Vec<&str> = tags_str.split(',').map(str::trim).collect();
-        for tag_name in tag_names {
-            println!("処理中のタグ: '{tag_name}'");
-            if !tag_name.is_empty() {
-                // 1. タグID取得/作成
-                let tag_id = get_or_create_tag(&db, tag_name)?;
-
-                // 2. 記事-タグ関連を作成
-                println!("article_tags への INSERT: article_id={article_id}, tag_id={tag_id}");
-
-                match db.execute(
-                    "INSERT INTO article_tags (article_id, tag_id) VALUES (?, ?)",
-                    params![article_id, tag_id],
-                ) {
-                    Ok(rows) => println!("article_tags INSERT 成功: {rows} rows"),
-                    Err(e) => println!("article_tags INSERT エラー: {e}"),
-                }
-            }
-        }
-    } else {
-        println!("タグなし（None）");
-    }
-
-    println!("記事保存完了: {result_status}");
-    Ok(result_status)
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    store.save_article(request)
 }
 
+// 記事の編集（IDで指定）。URLの変更が別の記事と重なる場合は、merge: true なら統合する
 #[tauri::command]
 #[allow(clippy::needless_pass_by_value)]
 #[allow(clippy::significant_drop_tightening)]
-fn update_article(state: State<AppState>, request: SaveArticleRequest) -> Result<(), String> {
-    println!("記事編集開始: {}", request.url);
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-
-    // ph.1 更新対象の記事ID特定
-    let article_id = get_article_id_by_url(&db, &request.url)?;
-
-    // ph.2 記事の基本情報を更新
-    db.execute(
-        "UPDATE articles SET title = ?, url = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
-        params![request.title, request.url, article_id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    println!("記事基本情報更新完了");
-
-    // ph.3 既存のタグ-記事リレーションを削除
-    db.execute(
-        "DELETE FROM article_tags WHERE article_id = ?",
-        params![article_id],
-    )
-    .map_err(|e| e.to_string())?;
-    println!("既存タグ関連削除完了");
-
-    // ph.4 タグ-記事リレーションを改めて登録
-    if let Some(tags_str) = request.tags {
-        let tag_names: Vec<&str> = tags_str.split(',').map(str::trim).collect();
-        for tag_name in tag_names {
-            if !tag_name.is_empty() {
-                let tag_id = get_or_create_tag(&db, tag_name)?;
-                db.execute(
-                    "INSERT INTO article_tags (article_id, tag_id) VALUES (?, ?)",
-                    params![article_id, tag_id],
-                )
-                .map_err(|e| e.to_string())?;
-            }
-        }
-        println!("新しいタグ登録完了");
-    }
-    println!("記事編集完了");
-    Ok(())
+fn update_article(
+    state: State<AppState>,
+    id: i64,
+    request: SaveArticleRequest,
+    merge: Option<bool>,
+) -> Result<UpdateResult, String> {
+    println!("記事編集開始: ID {id}");
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    store.update_article(id, &request, merge.unwrap_or(false))
 }
 
 #[tauri::command]
 #[allow(clippy::needless_pass_by_value)]
 #[allow(clippy::significant_drop_tightening)]
-fn delete_article(state: State<AppState>, url: String) -> Result<(), String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-
-    db.execute("DELETE FROM articles WHERE url = ?", params![url])
-        .map_err(|e| e.to_string())?;
+fn delete_article(state: State<AppState>, id: i64) -> Result<(), String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
 
-    Ok(())
+    // 完全には消さずゴミ箱へ移す（タグやメモは復元時のために残す）
+    store.delete_article(id)
 }
 
 #[tauri::command]
 #[allow(clippy::needless_pass_by_value)]
 fn open_url(url: String) -> Result<(), String> {
-    use std::process::Command;
-
-    #[cfg(target_os = "windows")]
-    {
-        Command::new("cmd")
-            .args(["/c", "start", &url])
-            .spawn()
-            .map_err(|e| e.to_string())?;
-    }
-
-    #[cfg(target_os = "macos")]
-    {
-        Command::new("open")
-            .arg(&url)
-            .spawn()
-            .map_err(|e| e.to_string())?;
-    }
-
-    #[cfg(target_os = "linux")]
-    {
-        Command::new("xdg-open")
-            .arg(&url)
-            .spawn()
-            .map_err(|e| e.to_string())?;
-    }
-
-    Ok(())
+    config::open_url(&url)
 }
 
 #[tauri::command]
 #[allow(clippy::needless_pass_by_value)]
-fn save_active_page(state: State<AppState>) -> Result<String, String> {
+fn save_active_page(state: State<AppState>) -> Result<SaveResult, String> {
     println!("自動保存開始...");
 
     let browser_info = match get_active_browser_info() {
@@ -951,7 +828,10 @@ fn save_active_page(state: State<AppState>) -> Result<String, String> {
     };
 
     // タグ自動生成
-    let auto_tags = auto_tagging(browser_info.url.clone());
+    let auto_tags = {
+        let rules = state.tag_rules.read().map_err(|e| e.to_string())?;
+        auto_tagging(&rules, &browser_info.url, &browser_info.title)
+    };
     println!("生成されたタグ: {auto_tags}");
 
     let request = SaveArticleRequest {
@@ -971,334 +851,454 @@ fn save_active_page(state: State<AppState>) -> Result<String, String> {
     Ok(result)
 }
 
-// 人気タグを取得
+// URLとタイトルから自動で付くタグを確認する（ルールの動作確認用）
 #[tauri::command]
 #[allow(clippy::needless_pass_by_value)]
 #[allow(clippy::significant_drop_tightening)]
-fn get_popular_tags(state: State<AppState>, limit: Option<usize>) -> Result<Vec<TagCount>, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let limit = limit.unwrap_or(20);
-
-    let mut stmt = db
-        .prepare(
-            "SELECT
-           TRIM(t.name) as tag_name
-         , COUNT(*) as count
-        FROM tags t
-        JOIN article_tags at
-          ON t.id = at.tag_id
-        WHERE t.name != 'auto-saved' --暫定
-        GROUP BY TRIM(t.name) 
-        ORDER BY count DESC, t.name ASC
-        LIMIT ?",
+fn preview_auto_tags(
+    state: State<AppState>,
+    url: String,
+    title: String,
+) -> Result<AutoTagPreview, String> {
+    let url = {
+        let store = state.store.lock().map_err(|e| e.to_string())?;
+        store.url_rules().normalize(&url)
+    };
+    let rules = state.tag_rules.read().map_err(|e| e.to_string())?;
+    let (tags, matched_rules) = auto_tag_list(&rules, &url, &title);
+    Ok(AutoTagPreview {
+        tags,
+        matched_rules,
+    })
+}
+
+// ルールファイルを読み直す（誤りがあれば今のルールのまま）。ルール数を返す
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+fn reload_tag_rules(state: State<AppState>) -> Result<usize, String> {
+    let rules = TagRules::load(&state.tag_rules_path)?;
+    let count = rules.len();
+    *state.tag_rules.write().map_err(|e| e.to_string())? = rules;
+    Ok(count)
+}
+
+// 全文検索インデックスを articles から作り直す
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn rebuild_search_index(state: State<AppState>) -> Result<(), String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+
+    store.write(|db| {
+        db.execute(
+            "INSERT INTO articles_fts(articles_fts) VALUES ('rebuild')",
+            [],
         )
-        .map_err(|e| e.to_string())?;
-
-    let tag_counts = stmt
-        .query_map([limit], |row| {
-            Ok(TagCount {
-                tag: row.get(0)?,
-                count: row.get(1)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| e.to_string())
+    })?;
 
-    let mut result = Vec::new();
-    for tag_count in tag_counts {
-        result.push(tag_count.map_err(|e| e.to_string())?);
-    }
+    println!("✅ 全文検索インデックス再構築完了");
+    Ok(())
+}
 
-    Ok(result)
+// 読書状況の統計（日ごと・週ごとの保存数、読了率、サイト上位、タグの推移、積読の経過日数）
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn get_statistics(
+    state: State<AppState>,
+    options: Option<StatisticsOptions>,
+) -> Result<Statistics, String> {
+    let store = state.store.lock().map_err(|e| e.to_string())?;
+    statistics::statistics(store.connection(), &options.unwrap_or_default())
 }
 
-//================================================================================================
-// コマンド関連ファンクション等 - Functions and Sub procedures for command actions
-//================================================================================================
+// 人気タグを取得（ステータス指定時はそのステータスの記事のみで集計）
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn get_popular_tags(
+    state: State<AppState>,
+    limit: Option<usize>,
+    status: Option<String>,
+) -> Result<Vec<TagCount>, String> {
+    let store = state.store.lock().map_err(|e| e.to_string())?;
+    store.popular_tags(limit.unwrap_or(20), status.as_deref())
+}
 
-// URL正規化（クエリパラメータ殺し）
-fn normalize_url(url: &str) -> String {
-    if url.starts_with("file://") {
-        // ローカルファイルの場合はそのまま返す
-        return url.to_string();
-    }
+// 読書ステータス一覧（記事数付き）
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn get_statuses(state: State<AppState>) -> Result<Vec<Status>, String> {
+    let store = state.store.lock().map_err(|e| e.to_string())?;
+    status::list_statuses(store.connection())
+}
 
-    match Url::parse(url) {
-        Ok(parsed_url) => {
-            let host = parsed_url.host_str().unwrap_or("");
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn create_status(state: State<AppState>, name: String) -> Result<i64, String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    store.write(|db| status::create_status(db, &name))
+}
 
-            // 一部のサイトではクエリパラメータを殺さない
-            if url_preserve_targets(host) {
-                return url.to_string();
-            }
-            format!(
-                "{}{}",
-                parsed_url.origin().ascii_serialization(),
-                parsed_url.path()
-            )
-        }
-        Err(_) => url.to_string(),
-    }
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn rename_status(state: State<AppState>, name: String, new_name: String) -> Result<(), String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    store.write(|db| status::rename_status(db, &name, &new_name))
 }
 
-// 登録サイトIDの特定
-fn get_or_create_site(db: &Connection, site_name: &str) -> Result<i64, String> {
-    // 登録済みサイトの検索（重複確認）
-    let mut stmt = db
-        .prepare("SELECT id FROM sites WHERE name = ?")
-        .map_err(|e| e.to_string())?;
-
-    let site_id_opt = stmt
-        .query_row([site_name], |row| row.get(0))
-        .optional()
-        .map_err(|e| e.to_string())?;
-    if let Some(site_id) = site_id_opt {
-        println!("既存サイト使用: {site_name} (ID: {site_id})");
-        Ok(site_id)
-    } else {
-        // 新しいサイトを作成（INSERT）
-        db.execute("INSERT INTO sites (name) VALUES (?)", params![site_name])
-            .map_err(|e| e.to_string())?;
-
-        // 作成したサイトのIDを取得
-        let site_id = db.last_insert_rowid();
-        println!("新規サイト作成: {site_name} (ID: {site_id})");
-        Ok(site_id)
-    }
+// ステータス削除（属する記事は move_to へ移す）
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn delete_status(
+    state: State<AppState>,
+    name: String,
+    move_to: Option<String>,
+) -> Result<(), String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    store.write(|db| status::delete_status(db, &name, move_to.as_deref()))
 }
 
-// 登録に使うタグの特定
-fn get_or_create_tag(db: &Connection, tag_name: &str) -> Result<i64, String> {
-    let mut stmt = db
-        .prepare("SELECT id FROM tags WHERE name = ?")
-        .map_err(|e| e.to_string())?;
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn reorder_statuses(state: State<AppState>, names: Vec<String>) -> Result<(), String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    store.write(|db| status::reorder_statuses(db, &names))
+}
 
-    let tag_id_opt = stmt
-        .query_row([tag_name], |row| row.get(0))
-        .optional()
-        .map_err(|e| e.to_string())?;
+// 記事を別のステータスへ移動
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn set_article_status(state: State<AppState>, id: i64, status: String) -> Result<(), String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    store.write(|db| status::set_article_status(db, id, &status))?;
+    REFRESH_NEEDED.store(true, Ordering::Relaxed);
+    Ok(())
+}
 
-    if let Some(tag_id) = tag_id_opt {
-        println!("既存タグ使用: {tag_name} (ID: {tag_id})");
-        Ok(tag_id)
-    } else {
-        // 新しいタグを作成
-        db.execute("INSERT INTO tags (name) VALUES (?)", [tag_name])
-            .map_err(|e| e.to_string())?;
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn get_article_status_history(
+    state: State<AppState>,
+    id: i64,
+) -> Result<Vec<StatusChange>, String> {
+    let store = state.store.lock().map_err(|e| e.to_string())?;
+    status::article_status_history(store.connection(), id)
+}
 
-        let tag_id = db.last_insert_rowid();
-        println!("新規タグ作成: {tag_name} (ID: {tag_id})");
-        Ok(tag_id)
-    }
+// 記事のメモ（Markdown）を取得
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn get_article_notes(state: State<AppState>, id: i64) -> Result<String, String> {
+    let store = state.store.lock().map_err(|e| e.to_string())?;
+    notes::get_notes(store.connection(), id)
 }
 
-// 記事IDをurlから求める
-fn get_article_id_by_url(db: &Connection, url: &str) -> Result<i64, String> {
-    let mut stmt = db
-        .prepare("SELECT id FROM articles WHERE url = ?")
-        .map_err(|e| e.to_string())?;
+// 記事のメモを保存（記事の更新日時も進む）
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn set_article_notes(state: State<AppState>, id: i64, notes: String) -> Result<(), String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    store.write(|db| notes::set_notes(db, id, &notes))
+}
 
-    stmt.query_row([url], |row| row.get(0))
-        .map_err(|e| e.to_string())
+// ゴミ箱内の記事一覧
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn get_trash(state: State<AppState>) -> Result<Vec<TrashedArticle>, String> {
+    let store = state.store.lock().map_err(|e| e.to_string())?;
+    trash::list_trash(store.connection())
 }
 
-fn url_preserve_targets(host: &str) -> bool {
-    //TODO 将来的には設定ファイルから読み込みたい
-    let url_preserve_sites = [
-        "youtube.com",
-        // あと何かあれば随時
-    ];
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn restore_article(state: State<AppState>, id: i64) -> Result<(), String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    store.write(|db| trash::restore_article(db, id))
+}
 
-    url_preserve_sites.iter().any(|&site| host.contains(site))
+// ゴミ箱を空にする（削除件数を返す）
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn empty_trash(state: State<AppState>) -> Result<usize, String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    store.write(trash::empty_trash)
 }
 
-fn load_config() -> Config {
-    let config_path = PathBuf::from("config.json");
+// URLの正規形が同じ記事の一覧（http/https・末尾スラッシュ・youtu.be など）
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn find_duplicate_articles(state: State<AppState>) -> Result<Vec<DuplicateGroup>, String> {
+    let store = state.store.lock().map_err(|e| e.to_string())?;
+    canonical::find_duplicates(store.connection())
+}
 
-    if config_path.exists() {
-        match fs::read_to_string(&config_path) {
-            Ok(content) => match serde_json::from_str::<Config>(&content) {
-                Ok(config) => {
-                    println!(
-                        "✅ 設定ファイル読み込み成功: database_path = {}",
-                        config.database_path
-                    );
-                    return config;
-                }
-                Err(e) => {
-                    eprintln!("⚠️ 設定ファイルのパースエラー: {e} - デフォルト設定を使用");
-                }
-            },
-            Err(e) => {
-                eprintln!("⚠️ 設定ファイル読み込みエラー: {e} - デフォルト設定を使用");
-            }
-        }
-    } else {
-        println!("ℹ️ 設定ファイルが見つかりません - デフォルト設定を使用");
-    }
+// 重複した記事を統合（canonical_url を省略するとすべて）。削除した記事数を返す
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn merge_duplicate_articles(
+    state: State<AppState>,
+    canonical_url: Option<String>,
+) -> Result<usize, String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    let merged = store.write(|db| canonical::merge_duplicates(db, canonical_url.as_deref()))?;
+    REFRESH_NEEDED.store(true, Ordering::Relaxed);
+    Ok(merged)
+}
 
-    // デフォルト設定
-    Config {
-        database_path: "atode.db".to_string(),
-    }
+// 一緒に付いていることの多いタグ（編集時のタグ候補にも使う）
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn get_related_tags(
+    state: State<AppState>,
+    tag: String,
+    limit: Option<usize>,
+) -> Result<Vec<RelatedTag>, String> {
+    let store = state.store.lock().map_err(|e| e.to_string())?;
+    tag_graph::related_tags(store.connection(), &tag, limit.unwrap_or(10))
 }
 
-fn init_database(db_path: &str) -> Result<Connection> {
-    println!("📂 データベースパス: {db_path}");
-    let conn = Connection::open(db_path)?;
+// タグの共起グラフ（ノードと重み付きの辺）
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn get_tag_graph(state: State<AppState>, min_weight: Option<u32>) -> Result<TagGraph, String> {
+    let store = state.store.lock().map_err(|e| e.to_string())?;
+    tag_graph::tag_graph(store.connection(), min_weight.unwrap_or(1))
+}
 
-    conn.execute("PRAGMA foreign_keys = ON;", [])?;
+// タグ階層をツリーで取得（子孫を含めた記事数付き）
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn get_tag_tree(state: State<AppState>) -> Result<Vec<TagNode>, String> {
+    let store = state.store.lock().map_err(|e| e.to_string())?;
+    tags::tag_tree(store.connection())
+}
 
-    let ddl_files = [
-        include_str!("ddl/001_create_sites.sql"),
-        include_str!("ddl/002_create_articles.sql"),
-        include_str!("ddl/003_create_tags.sql"),
-        include_str!("ddl/004_create_article_tags.sql"),
-    ];
+// タグの親を設定（子孫タグごと移動。parent未指定でトップレベルへ）
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn set_tag_parent(
+    state: State<AppState>,
+    tag: String,
+    parent: Option<String>,
+) -> Result<(), String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    store.write(|db| tags::set_tag_parent(db, &tag, parent.as_deref()))
+}
 
-    for ddl in &ddl_files {
-        conn.execute(ddl, [])?;
-    }
+// タグ名を変更（全記事に反映される）
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn rename_tag(state: State<AppState>, name: String, new_name: String) -> Result<(), String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    store.write(|db| tags::rename_tag(db, &name, &new_name))?;
+    REFRESH_NEEDED.store(true, Ordering::Relaxed);
+    Ok(())
+}
 
-    Ok(conn)
+// 複数のタグを1つに統合（統合元のタグは削除される）
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn merge_tags(state: State<AppState>, sources: Vec<String>, target: String) -> Result<(), String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    store.write(|db| tags::merge_tags(db, &sources, &target))?;
+    REFRESH_NEEDED.store(true, Ordering::Relaxed);
+    Ok(())
 }
 
-// 自動タグ付け
+// タグの別名一覧
+#[tauri::command]
 #[allow(clippy::needless_pass_by_value)]
-fn auto_tagging(url: String) -> String {
-    let mut tags: Vec<String> = Vec::with_capacity(3);
-
-    // URLクレートでサイト名を抽出
-    if let Ok(parsed_url) = Url::parse(&url) {
-        if let Some(host) = parsed_url.host_str() {
-            //ホスト名クリーンアップ（よくあるprefix, suffix排除）
-            let clean_site = clean_hostname(host);
-
-            if clean_site.len() > 1 {
-                // サイト名のタグを追加
-                tags.push(clean_site.clone());
-                // 推奨タグの自動付与
-                add_essential_tags(&mut tags, &clean_site);
-            }
-        }
-    }
-    // 空の場合は空文字を返す
-    if tags.is_empty() {
-        return String::new();
-    }
+#[allow(clippy::significant_drop_tightening)]
+fn get_tag_aliases(state: State<AppState>) -> Result<Vec<TagAlias>, String> {
+    let store = state.store.lock().map_err(|e| e.to_string())?;
+    tags::list_aliases(store.connection())
+}
 
-    // 重複削除とソート
-    tags.sort_unstable();
-    tags.dedup();
+// 別名を登録（例: js → javascript。以後 js で保存すると javascript が付く）
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn add_tag_alias(state: State<AppState>, alias: String, tag: String) -> Result<(), String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    store.write(|db| tags::add_alias(db, &alias, &tag))
+}
 
-    tags.join(", ")
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn remove_tag_alias(state: State<AppState>, alias: String) -> Result<(), String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    store.write(|db| tags::remove_alias(db, &alias))
 }
 
-fn clean_hostname(host: &str) -> String {
-    let mut input = host.to_lowercase();
+// サイト一覧（記事数付き）
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn get_sites(state: State<AppState>) -> Result<Vec<SiteSummary>, String> {
+    let store = state.store.lock().map_err(|e| e.to_string())?;
+    sites::list_sites(store.connection())
+}
 
-    // prefix(www.など）除去
-    if let Some(captures) = PREFIX_RE.find(&input) {
-        input.drain(0..captures.end());
-    }
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn rename_site(state: State<AppState>, name: String, new_name: String) -> Result<(), String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    store.write(|db| sites::rename_site(db, &name, &new_name))?;
+    REFRESH_NEEDED.store(true, Ordering::Relaxed);
+    Ok(())
+}
 
-    // suffix除去（co.jpみたいなの→.comみたいなのの順）
-    if let Some(captures) = COMPOUND_TLD_RE.find(&input) {
-        input.truncate(captures.start());
-    } else if let Some(captures) = TLD_RE.find(&input) {
-        input.truncate(captures.start());
-    }
+// 複数のサイトを1つに統合（例: m.youtube.com, youtu.be → youtube.com）
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn merge_sites(state: State<AppState>, sources: Vec<String>, target: String) -> Result<(), String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    store.write(|db| sites::merge_sites(db, &sources, &target))?;
+    REFRESH_NEEDED.store(true, Ordering::Relaxed);
+    Ok(())
+}
 
-    input
+// ホスト名の別名一覧
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn get_site_aliases(state: State<AppState>) -> Result<Vec<SiteAlias>, String> {
+    let store = state.store.lock().map_err(|e| e.to_string())?;
+    sites::list_aliases(store.connection())
 }
 
-#[inline]
-fn add_essential_tags(tags: &mut Vec<String>, site: &str) {
-    match site {
-        // AI系
-        "claude" | "chatgpt" | "openai" | "anthropic" | "gemini" => {
-            tags.push("ai".to_string());
-        }
+// ホスト名の別名を登録（以後そのホストから保存した記事は site に紐づく）
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn add_site_alias(state: State<AppState>, host: String, site: String) -> Result<(), String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    store.write(|db| sites::add_alias(db, &host, &site))
+}
 
-        // プログラミング系
-        "github" | "gitlab" | "stackoverflow" | "qiita" | "zenn" => {
-            tags.push("programming".to_string());
-        }
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn remove_site_alias(state: State<AppState>, host: String) -> Result<(), String> {
+    let mut store = state.store.lock().map_err(|e| e.to_string())?;
+    store.write(|db| sites::remove_alias(db, &host))
+}
 
-        // リファレンス系
-        "wikipedia" | "mdn" => {
-            tags.push("reference".to_string());
-        }
+// 保存した検索の一覧（現在の一致件数付き）
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn get_saved_searches(state: State<AppState>) -> Result<Vec<SavedSearch>, String> {
+    let store = state.store.lock().map_err(|e| e.to_string())?;
+    saved_searches::list_saved_searches(store.connection())
+}
 
-        // 動画系
-        "youtube" | "vimeo" | "twitch" => {
-            tags.push("video".to_string());
-        }
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+fn create_saved_search(
+    app: AppHandle,
+    state: State<AppState>,
+    name: String,
+    filters: SearchFilters,
+) -> Result<i64, String> {
+    let id = {
+        let mut store = state.store.lock().map_err(|e| e.to_string())?;
+        store.write(|db| saved_searches::create_saved_search(db, &name, filters))?
+    };
+    refresh_tray_menu(&app);
+    Ok(id)
+}
 
-        // ソーシャル系
-        "twitter" | "x" | "reddit" | "facebook" => {
-            tags.push("social".to_string());
-        }
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+fn update_saved_search(
+    app: AppHandle,
+    state: State<AppState>,
+    id: i64,
+    name: String,
+    filters: SearchFilters,
+) -> Result<(), String> {
+    {
+        let mut store = state.store.lock().map_err(|e| e.to_string())?;
+        store.write(|db| saved_searches::update_saved_search(db, id, &name, filters))?;
+    }
+    refresh_tray_menu(&app);
+    Ok(())
+}
 
-        // ショッピング系
-        "amazon" | "rakuten" => {
-            tags.push("shopping".to_string());
-        }
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+fn delete_saved_search(app: AppHandle, state: State<AppState>, id: i64) -> Result<(), String> {
+    {
+        let mut store = state.store.lock().map_err(|e| e.to_string())?;
+        store.write(|db| saved_searches::delete_saved_search(db, id))?;
+    }
+    refresh_tray_menu(&app);
+    Ok(())
+}
 
-        // その他は追加タグなし（サイト名だけで十分）
-        _ => {}
+// トレイメニューで選ばれた保存済み検索を取り出す（選ばれていなければ None）
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::significant_drop_tightening)]
+fn take_pending_saved_search(state: State<AppState>) -> Result<Option<SavedSearch>, String> {
+    let id = PENDING_SAVED_SEARCH.swap(0, Ordering::Relaxed);
+    if id == 0 {
+        return Ok(None);
     }
+    let store = state.store.lock().map_err(|e| e.to_string())?;
+    saved_searches::get_saved_search(store.connection(), id).map(Some)
 }
 
 //================================================================================================
-// tests
+// コマンド関連ファンクション等 - Functions and Sub procedures for command actions
 //================================================================================================
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_url_normalization() {
-        // URL正規化テスト（通常）
-        assert_eq!(
-            normalize_url("https://example.com/page?ref=123"),
-            "https://example.com/page"
-        );
-        assert_eq!(
-            normalize_url("https://github.com/user/repo"),
-            "https://github.com/user/repo"
-        );
-
-        // 特別扱い（GETパラメータを殺さない）ケース
-        assert_eq!(
-            normalize_url("https://www.youtube.com/watch?v=a1b2c3d4e5"),
-            "https://www.youtube.com/watch?v=a1b2c3d4e5"
-        );
-
-        // ローカルファイル
-        assert_eq!(
-            normalize_url("file:///C:/Users/test/document.html"),
-            "file:///C:/Users/test/document.html"
-        );
-    }
 
-    #[test]
-    fn test_auto_tagging() {
-        assert_eq!(
-            // prefix, suffix除去（単独）
-            auto_tagging("https://www.sample.com".to_string()),
-            "sample"
-        );
-
-        assert_eq!(
-            // suffix除去（複合）
-            auto_tagging("https://www.example.co.jp".to_string()),
-            "example"
-        );
-
-        assert_eq!(
-            // 推奨タグ
-            auto_tagging("https://github.com/user".to_string()),
-            "github, programming"
-        )
+fn load_config() -> Config {
+    let config_path = PathBuf::from(config::CONFIG_FILE);
+
+    if config_path.exists() {
+        match Config::read(&config_path) {
+            Ok(config) => {
+                println!(
+                    "✅ 設定ファイル読み込み成功: database_path = {}",
+                    config.database_path
+                );
+                return config;
+            }
+            Err(e) => {
+                eprintln!("⚠️ 設定ファイル読み込みエラー: {e} - デフォルト設定を使用");
+            }
+        }
+    } else {
+        println!("ℹ️ 設定ファイルが見つかりません - デフォルト設定を使用");
     }
+
+    // デフォルト設定
+    Config::default()
 }
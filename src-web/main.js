@@ -1,18 +1,27 @@
 // Tauri 2.0 API
 const invoke = window.__TAURI_INTERNALS__.invoke;
 
+// 1回に読み込む記事数
+const PAGE_SIZE = 50;
+
 class AtodeApp {
     constructor() {
         this.articles = [];
-        this.editingUrl = null;
+        this.totalArticles = 0;
+        this.currentFilters = {};
+        this.randomSeed = 0;
+        this.editingId = null;
         this.popularTags = [];
         this.popularSites = [];
+        this.statuses = [];
+        this.savedSearches = [];
         this._confirmResolve = null;
         this.init();
     }
 
     async init() {
         this.loadTheme();
+        await this.loadStatuses();
         await this.loadArticles();
         await this.loadPopularTags();
         this.setupEventListeners();
@@ -46,10 +55,12 @@ class AtodeApp {
 
     setupEventListeners() {
         // Enter キーでの検索
+        const textSearch = document.getElementById('textSearch');
         const tagSearch = document.getElementById('tagSearch');
         const siteSearch = document.getElementById('siteSearch');
+        const querySearch = document.getElementById('querySearch');
         
-        [tagSearch, siteSearch].forEach(input => {
+        [textSearch, tagSearch, siteSearch, querySearch].forEach(input => {
             if (input) {
                 input.addEventListener('keypress', (e) => {
                     if (e.key === 'Enter') {
@@ -59,6 +70,23 @@ class AtodeApp {
             }
         });
 
+        // タグ入力に合わせて関連タグの候補を更新（入力が止まってから問い合わせる）
+        let relatedTagsTimer = null;
+        document.getElementById('tagsInput')?.addEventListener('input', () => {
+            clearTimeout(relatedTagsTimer);
+            relatedTagsTimer = setTimeout(() => this.updateRelatedTags(), 300);
+        });
+
+        // 子タグを含めるかの切り替えで即検索
+        document.getElementById('includeDescendants')?.addEventListener('change', () => this.searchArticles());
+
+        // 並び順の変更
+        document.getElementById('sortSelect')?.addEventListener('change', () => this.changeSort());
+
+        // ステータス選択で即検索
+        document.getElementById('statusFilter')?.addEventListener('change', () => this.searchArticles());
+        document.getElementById('periodFilter')?.addEventListener('change', () => this.searchArticles());
+
         // モーダルの外側クリックで閉じる
         const modal = document.getElementById('articleModal');
         if (modal) {
@@ -69,6 +97,36 @@ class AtodeApp {
             });
         }
 
+        // ゴミ箱モーダルの外側クリックで閉じる
+        const trashModal = document.getElementById('trashModal');
+        if (trashModal) {
+            trashModal.addEventListener('click', (e) => {
+                if (e.target === trashModal) {
+                    this.closeTrash();
+                }
+            });
+        }
+
+        // 統計モーダルの外側クリックで閉じる
+        const statisticsModal = document.getElementById('statisticsModal');
+        if (statisticsModal) {
+            statisticsModal.addEventListener('click', (e) => {
+                if (e.target === statisticsModal) {
+                    this.closeStatistics();
+                }
+            });
+        }
+
+        // 保存した検索モーダルの外側クリックで閉じる
+        const savedSearchModal = document.getElementById('savedSearchModal');
+        if (savedSearchModal) {
+            savedSearchModal.addEventListener('click', (e) => {
+                if (e.target === savedSearchModal) {
+                    this.closeSavedSearches();
+                }
+            });
+        }
+
         // 確認モーダルのボタン・外側クリック
         const confirmModal = document.getElementById('confirmModal');
         document.getElementById('confirmOkBtn')?.addEventListener('click', () => this.resolveConfirm(true));
@@ -86,6 +144,9 @@ class AtodeApp {
             if (e.key === 'Escape') {
                 this.resolveConfirm(false);
                 this.closeModal();
+                this.closeTrash();
+                this.closeSavedSearches();
+                this.closeStatistics();
             }
         });
     }
@@ -124,12 +185,46 @@ class AtodeApp {
                     await this.loadArticles();
                     await this.loadPopularTags();
                 }
+                // トレイメニューから選ばれた保存済み検索
+                const savedSearch = await invoke('take_pending_saved_search');
+                if (savedSearch) {
+                    await this.applySavedSearch(savedSearch);
+                }
             } catch (error) {
                 console.error('リフレッシュチェックエラー:', error);
             }
         }, 500);
     }
 
+    async loadStatuses() {
+        try {
+            this.statuses = await invoke('get_statuses');
+            const statusFilter = document.getElementById('statusFilter');
+            if (statusFilter) {
+                const current = statusFilter.value;
+                statusFilter.innerHTML = '<option value="">📥 すべてのステータス</option>' +
+                    this.statuses.map(s =>
+                        `<option value="${this.escapeHtml(s.name)}">${this.escapeHtml(s.name)} (${s.article_count})</option>`
+                    ).join('');
+                statusFilter.value = current;
+            }
+        } catch (error) {
+            console.error('ステータス読み込みエラー:', error);
+            this.statuses = [];
+        }
+    }
+
+    async changeStatus(id, status) {
+        try {
+            await invoke('set_article_status', { id, status });
+            await this.loadStatuses();
+            await this.searchArticles();
+        } catch (error) {
+            console.error('ステータス変更エラー:', error);
+            this.showError('ステータスの変更に失敗しました');
+        }
+    }
+
     async loadPopularTags() {
         try {
             this.popularTags = await invoke('get_popular_tags', { limit: 15 });
@@ -143,9 +238,13 @@ class AtodeApp {
     async loadArticles() {
         try {
             // 検索条件をクリア
+            const textSearch = document.getElementById('textSearch');
             const tagSearch = document.getElementById('tagSearch');
             const siteSearch = document.getElementById('siteSearch');
             
+            if (textSearch) {
+                textSearch.value = '';
+            }
             if (tagSearch) {
                 tagSearch.value = '';
                 tagSearch.placeholder = '🏷️ タグで検索 (カンマ区切り入力)'; // プレースホルダーリセット
@@ -154,10 +253,22 @@ class AtodeApp {
                 siteSearch.value = '';
                 siteSearch.placeholder = '🌐 サイトで検索(例：google)';
             }
+            const querySearch = document.getElementById('querySearch');
+            if (querySearch) {
+                querySearch.value = '';
+            }
+            const statusFilter = document.getElementById('statusFilter');
+            if (statusFilter) {
+                statusFilter.value = '';
+            }
+            const periodFilter = document.getElementById('periodFilter');
+            if (periodFilter) {
+                periodFilter.value = '';
+            }
             
-            this.articles = await invoke('get_articles');
-            this.renderArticles();
-            console.log(`📚 ${this.articles.length}件の記事を読み込みました`);
+            this.currentFilters = {};
+            await this.fetchArticles();
+            console.log(`📚 ${this.totalArticles}件中${this.articles.length}件の記事を読み込みました`);
         } catch (error) {
             console.error('記事の読み込みエラー:', error);
             this.showError('記事の読み込みに失敗しました');
@@ -165,25 +276,77 @@ class AtodeApp {
     }
 
     async searchArticles() {
+        const text = document.getElementById('textSearch')?.value.trim();
         const tagQuery = document.getElementById('tagSearch')?.value.trim();
         const site = document.getElementById('siteSearch')?.value.trim();
+        const status = document.getElementById('statusFilter')?.value;
+        // 相対指定（last 7 days など）はバックエンドがローカル時刻で解釈する
+        const period = document.getElementById('periodFilter')?.value;
+        const query = document.getElementById('querySearch')?.value.trim();
 
         const filters = {};
+        if (text) filters.text = text;
+        if (query) {
+            filters.query = query;
+            filters.include_descendants = document.getElementById('includeDescendants')?.checked ?? false;
+        }
         if (tagQuery) {
             // カンマ+スペースをカンマに統一して小文字化
             const normalizedTags = normalizeTagString(tagQuery).toLowerCase();
             filters.tag_query = normalizedTags;
+            filters.include_descendants = document.getElementById('includeDescendants')?.checked ?? false;
         }
         
         if (site) filters.site = site;
+        if (status) filters.status = status;
+        if (period) filters.created_after = period;
 
         try {
-            this.articles = await invoke('get_articles', { filters });
-            this.renderArticles();
-            console.log(`🔍 検索結果: ${this.articles.length}件`);
+            this.currentFilters = filters;
+            await this.fetchArticles();
+            console.log(`🔍 検索結果: ${this.totalArticles}件`);
         } catch (error) {
             console.error('検索エラー:', error);
-            this.showError('検索に失敗しました');
+            // 検索式の誤りは位置付きのメッセージをそのまま表示
+            this.showError(query ? String(error) : '検索に失敗しました');
+        }
+    }
+
+    // 現在の検索条件・並び順で記事を取得（append = true なら続きのページを追加）
+    async fetchArticles(append = false) {
+        const [sort, order] = (document.getElementById('sortSelect')?.value || 'default').split(':');
+        const filters = {
+            ...this.currentFilters,
+            limit: PAGE_SIZE,
+            offset: append ? this.articles.length : 0,
+        };
+        if (sort !== 'default') filters.sort = sort;
+        if (order) filters.order = order;
+        if (sort === 'random') filters.seed = this.randomSeed;
+
+        const page = await invoke('get_articles', { filters });
+        this.articles = append ? this.articles.concat(page.articles) : page.articles;
+        this.totalArticles = page.total;
+        this.renderArticles();
+    }
+
+    async loadMoreArticles() {
+        try {
+            await this.fetchArticles(true);
+        } catch (error) {
+            console.error('記事の読み込みエラー:', error);
+            this.showError('記事の読み込みに失敗しました');
+        }
+    }
+
+    async changeSort() {
+        // ランダム順は選び直すたびに並びを変える（ページ送り中は同じシードを使う）
+        this.randomSeed = Math.floor(Math.random() * 2 ** 31);
+        try {
+            await this.fetchArticles();
+        } catch (error) {
+            console.error('並び替えエラー:', error);
+            this.showError('記事の並び替えに失敗しました');
         }
     }
 
@@ -203,6 +366,15 @@ class AtodeApp {
         }
 
         container.innerHTML = this.articles.map(article => this.renderArticle(article)).join('');
+        if (this.articles.length < this.totalArticles) {
+            container.innerHTML += `
+                <div class="load-more">
+                    <button onclick="app.loadMoreArticles()">
+                        さらに表示（${this.articles.length} / ${this.totalArticles}件）
+                    </button>
+                </div>
+            `;
+        }
     }
 
     renderArticle(article) {
@@ -227,6 +399,7 @@ class AtodeApp {
                 <div class="article-title" onclick="app.openArticle('${this.escapeHtml(article.url)}')">
                     ${this.escapeHtml(article.title)}
                 </div>
+                ${article.snippet ? `<div class="article-snippet">${this.renderSnippet(article.snippet)}</div>` : ''}
                 <div class="article-meta">
                     <span class="site-link" onclick="app.addToSiteSearch('${this.escapeHtml(article.site_name)}')" title="このサイトで検索">
                         ${this.escapeHtml(article.site_name)}
@@ -234,10 +407,15 @@ class AtodeApp {
                 </div>
                 <div class="article-tags">${tagsHtml}</div>
                 <div class="article-actions">
-                    <button class="btn-small" onclick="app.editArticle('${this.escapeHtml(article.url)}')">
+                    <select onchange="app.changeStatus(${article.id}, this.value)" title="読書ステータス">
+                        ${this.statuses.map(s =>
+                            `<option value="${this.escapeHtml(s.name)}" ${s.name === article.status ? 'selected' : ''}>${this.escapeHtml(s.name)}</option>`
+                        ).join('')}
+                    </select>
+                    <button class="btn-small" onclick="app.editArticle(${article.id})">
                         編集
                     </button>
-                    <button class="btn-small btn-danger" onclick="app.deleteArticle('${this.escapeHtml(article.url)}')">
+                    <button class="btn-small btn-danger" onclick="app.deleteArticle(${article.id})">
                         削除
                     </button>
                 </div>
@@ -255,12 +433,15 @@ class AtodeApp {
     }
 
     showAddModal() {
-        this.editingUrl = null;
+        this.editingId = null;
         this.resetForm();
         
         const modal = document.getElementById('articleModal');
         const modalTitle = document.getElementById('modalTitle');
+        const notesGroup = document.getElementById('notesGroup');
         
+        // メモは保存済みの記事にのみ付けられる
+        if (notesGroup) notesGroup.style.display = 'none';
         if (modalTitle) modalTitle.textContent = '記事を追加';
         if (modal) modal.style.display = 'block';
         
@@ -270,21 +451,25 @@ class AtodeApp {
         urlInput?.focus();
     }
 
-    async editArticle(url) {
-        const article = this.articles.find(a => a.url === url);
+    async editArticle(id) {
+        const article = this.articles.find(a => a.id === id);
         if (!article) return;
 
-        this.editingUrl = url;
+        this.editingId = article.id;
         
         const urlInput = document.getElementById('urlInput');
         const titleInput = document.getElementById('titleInput');
         const tagsInput = document.getElementById('tagsInput');
+        const notesInput = document.getElementById('notesInput');
+        const notesGroup = document.getElementById('notesGroup');
         const modalTitle = document.getElementById('modalTitle');
         const modal = document.getElementById('articleModal');
 
         if (urlInput) urlInput.value = article.url;
         if (titleInput) titleInput.value = article.title;
         if (tagsInput) tagsInput.value = article.tags || '';
+        if (notesInput) notesInput.value = article.notes || '';
+        if (notesGroup) notesGroup.style.display = '';
         if (modalTitle) modalTitle.textContent = '記事を編集';
         if (modal) modal.style.display = 'block';
 
@@ -292,24 +477,255 @@ class AtodeApp {
         titleInput?.focus();
     }
 
-    async deleteArticle(url) {
-        if (!(await this.confirmDialog('この記事を削除しますか？'))) return;
+    async deleteArticle(id) {
+        if (!(await this.confirmDialog('この記事をゴミ箱へ移しますか？'))) return;
 
         try {
-            await invoke('delete_article', { url });
+            await invoke('delete_article', { id });
             await this.loadArticles();
             await this.loadPopularTags();
-            this.showSuccess('記事を削除しました');
+            this.showSuccess('記事をゴミ箱へ移しました（ゴミ箱から復元できます）');
         } catch (error) {
             console.error('削除エラー:', error);
             this.showError('記事の削除に失敗しました');
         }
     }
 
+    async showTrash() {
+        const modal = document.getElementById('trashModal');
+        const list = document.getElementById('trashList');
+        if (!modal || !list) return;
+
+        try {
+            const trash = await invoke('get_trash');
+            list.innerHTML = trash.length === 0
+                ? '<div class="empty-state">ゴミ箱は空です</div>'
+                : trash.map(article => `
+                    <div class="trash-item">
+                        <div>
+                            <div class="article-title">${this.escapeHtml(article.title)}</div>
+                            <div class="article-meta">${this.escapeHtml(article.site_name || '')} • 削除: ${this.escapeHtml(new Date(article.deleted_at).toLocaleString('ja-JP', {
+                                year: 'numeric',
+                                month: 'short',
+                                day: 'numeric',
+                                hour: '2-digit',
+                                minute: '2-digit'
+                            }))}</div>
+                        </div>
+                        <button class="btn-small" onclick="app.restoreArticle(${article.id})">復元</button>
+                    </div>
+                `).join('');
+            modal.style.display = 'block';
+        } catch (error) {
+            console.error('ゴミ箱取得エラー:', error);
+            this.showError('ゴミ箱の取得に失敗しました');
+        }
+    }
+
+    closeTrash() {
+        const modal = document.getElementById('trashModal');
+        if (modal) modal.style.display = 'none';
+    }
+
+    async restoreArticle(id) {
+        try {
+            await invoke('restore_article', { id });
+            await this.showTrash();
+            await this.loadArticles();
+            await this.loadPopularTags();
+            this.showSuccess('記事を復元しました');
+        } catch (error) {
+            console.error('復元エラー:', error);
+            this.showError('記事の復元に失敗しました');
+        }
+    }
+
+    async emptyTrash() {
+        if (!(await this.confirmDialog('ゴミ箱の記事を完全に削除しますか？（元に戻せません）'))) return;
+
+        try {
+            const count = await invoke('empty_trash');
+            await this.showTrash();
+            this.showSuccess(`${count}件の記事を完全に削除しました`);
+        } catch (error) {
+            console.error('ゴミ箱削除エラー:', error);
+            this.showError('ゴミ箱を空にできませんでした');
+        }
+    }
+
+    async showStatistics() {
+        const modal = document.getElementById('statisticsModal');
+        const content = document.getElementById('statisticsContent');
+        if (!modal || !content) return;
+
+        try {
+            const stats = await invoke('get_statistics', { options: { days: 30 } });
+            const percent = (ratio) => `${Math.round(ratio.ratio * 100)}% (${ratio.read}/${ratio.saved})`;
+            const bars = (rows, label, count) => {
+                const max = Math.max(1, ...rows.map(count));
+                return rows.map(row => `
+                    <div class="article-meta">
+                        <span class="stat-bar" style="width: ${Math.round(count(row) / max * 160)}px"></span>
+                        ${this.escapeHtml(label(row))}: ${count(row)}
+                    </div>
+                `).join('');
+            };
+
+            content.innerHTML = `
+                <h4>✅ 読了率</h4>
+                <div class="article-meta">過去${stats.days}日間に保存: ${percent(stats.read_ratio)} • 全期間: ${percent(stats.overall_read_ratio)}</div>
+                <h4>📅 週ごとの保存数</h4>
+                ${bars(stats.saved_per_week, w => `${w.period}の週`, w => w.count)}
+                <h4>🌐 よく保存したサイト</h4>
+                ${bars(stats.top_sites, s => s.site || '(不明)', s => s.count)}
+                <h4>🏷️ タグの推移</h4>
+                ${stats.tag_growth.map(g => `
+                    <div class="article-meta">${g.week}の週: 新規${g.new_tags} / 合計${g.total_tags} ${g.top_tags.map(t => `#${this.escapeHtml(t.tag)}`).join(' ')}</div>
+                `).join('')}
+                <h4>📚 未読記事の経過日数</h4>
+                ${bars(stats.backlog_age, b => b.label, b => b.count)}
+            `;
+            modal.style.display = 'block';
+        } catch (error) {
+            console.error('統計取得エラー:', error);
+            this.showError('統計の取得に失敗しました');
+        }
+    }
+
+    closeStatistics() {
+        const modal = document.getElementById('statisticsModal');
+        if (modal) modal.style.display = 'none';
+    }
+
+    async showSavedSearches() {
+        const modal = document.getElementById('savedSearchModal');
+        const list = document.getElementById('savedSearchList');
+        if (!modal || !list) return;
+
+        try {
+            this.savedSearches = await invoke('get_saved_searches');
+            list.innerHTML = this.savedSearches.length === 0
+                ? '<div class="empty-state">保存した検索はありません</div>'
+                : this.savedSearches.map(search => `
+                    <div class="trash-item">
+                        <div>
+                            <div class="article-title">${this.escapeHtml(search.name)}</div>
+                            <div class="article-meta">${search.count}件</div>
+                        </div>
+                        <div style="display: flex; gap: 8px;">
+                            <button class="btn-small" onclick="app.applySavedSearchById(${search.id})">開く</button>
+                            <button class="btn-small" onclick="app.overwriteSavedSearch(${search.id})">上書き</button>
+                            <button class="btn-small btn-danger" onclick="app.deleteSavedSearch(${search.id})">削除</button>
+                        </div>
+                    </div>
+                `).join('');
+            modal.style.display = 'block';
+        } catch (error) {
+            console.error('保存した検索の取得エラー:', error);
+            this.showError('保存した検索の取得に失敗しました');
+        }
+    }
+
+    closeSavedSearches() {
+        const modal = document.getElementById('savedSearchModal');
+        if (modal) modal.style.display = 'none';
+    }
+
+    // 現在の検索条件と並び順（ページングは除く）
+    currentSearchFilters() {
+        const [sort, order] = (document.getElementById('sortSelect')?.value || 'default').split(':');
+        const filters = { ...this.currentFilters };
+        if (sort !== 'default') filters.sort = sort;
+        if (order) filters.order = order;
+        return filters;
+    }
+
+    async saveCurrentSearch() {
+        const nameInput = document.getElementById('savedSearchName');
+        const name = nameInput?.value.trim();
+        if (!name) {
+            this.showError('名前を入力してください');
+            return;
+        }
+
+        try {
+            await invoke('create_saved_search', { name, filters: this.currentSearchFilters() });
+            nameInput.value = '';
+            await this.showSavedSearches();
+            this.showSuccess(`「${name}」を保存しました`);
+        } catch (error) {
+            console.error('検索の保存エラー:', error);
+            this.showError(String(error));
+        }
+    }
+
+    async overwriteSavedSearch(id) {
+        const search = this.savedSearches.find(s => s.id === id);
+        if (!search) return;
+
+        try {
+            await invoke('update_saved_search', { id, name: search.name, filters: this.currentSearchFilters() });
+            await this.showSavedSearches();
+            this.showSuccess(`「${search.name}」を現在の条件で上書きしました`);
+        } catch (error) {
+            console.error('検索の上書きエラー:', error);
+            this.showError(String(error));
+        }
+    }
+
+    async deleteSavedSearch(id) {
+        const search = this.savedSearches.find(s => s.id === id);
+        if (!search) return;
+        if (!await this.confirmDialog(`保存した検索「${search.name}」を削除しますか？`)) return;
+
+        try {
+            await invoke('delete_saved_search', { id });
+            await this.showSavedSearches();
+        } catch (error) {
+            console.error('保存した検索の削除エラー:', error);
+            this.showError('保存した検索の削除に失敗しました');
+        }
+    }
+
+    async applySavedSearchById(id) {
+        const search = this.savedSearches.find(s => s.id === id);
+        if (!search) return;
+        this.closeSavedSearches();
+        await this.applySavedSearch(search);
+    }
+
+    // 保存した検索の条件を検索欄に反映して絞り込む
+    async applySavedSearch(search) {
+        const filters = search.filters || {};
+        const setValue = (id, value) => {
+            const element = document.getElementById(id);
+            if (element) element.value = value || '';
+        };
+        setValue('textSearch', filters.text);
+        setValue('tagSearch', filters.tag_query);
+        setValue('siteSearch', filters.site);
+        setValue('querySearch', filters.query);
+        setValue('statusFilter', filters.status);
+        setValue('periodFilter', filters.created_after);
+        const includeDescendants = document.getElementById('includeDescendants');
+        if (includeDescendants && filters.include_descendants !== undefined && filters.include_descendants !== null) {
+            includeDescendants.checked = filters.include_descendants;
+        }
+        const sortSelect = document.getElementById('sortSelect');
+        if (sortSelect) {
+            const value = filters.sort ? [filters.sort, filters.order].filter(Boolean).join(':') : 'default';
+            sortSelect.value = [...sortSelect.options].some(o => o.value === value) ? value : (filters.sort || 'default');
+        }
+
+        await this.searchArticles();
+        this.showSuccess(`🔖 ${search.name}（${this.totalArticles}件）`);
+    }
+
     async handleSubmit() {
         const urlInput = document.getElementById('urlInput');
         const titleInput = document.getElementById('titleInput');
         const tagsInput = document.getElementById('tagsInput');
+        const notesInput = document.getElementById('notesInput');
 
         if (!urlInput?.value.trim() || !titleInput?.value.trim()) {
             this.showError('URLとタイトルは必須です');
@@ -323,13 +739,25 @@ class AtodeApp {
         };
 
         try {
-            if (this.editingUrl) {
-                await invoke('update_article', {request });
-                this.showSuccess('記事を更新しました');
+            if (this.editingId) {
+                const id = this.editingId;
+                let result = await invoke('update_article', { id, request });
+                if (result.status === 'conflict') {
+                    // 変更後のURLが別の記事と同じなら、統合するか確認する
+                    const message = `「${result.article.title}」と同じURLです。2つの記事を統合しますか？（タグ・メモ・ステータスは引き継がれます）`;
+                    if (!(await this.confirmDialog(message))) return;
+                    result = await invoke('update_article', { id, request, merge: true });
+                }
+                const article = this.articles.find(a => a.id === id);
+                const notes = notesInput?.value ?? '';
+                if (article && article.notes !== notes) {
+                    await invoke('set_article_notes', { id, notes });
+                }
+                this.showSuccess(result.status === 'merged' ? '記事を統合しました' : '記事を更新しました');
             } else {
                 // save_articleも同様に修正
                 const result = await invoke('save_article', {request });
-                this.showSuccess(result === 'created' ? '記事を追加しました' : '記事を更新しました');
+                this.showSuccess(result.message);
             }
             
             this.closeModal();
@@ -345,7 +773,7 @@ class AtodeApp {
         const modal = document.getElementById('articleModal');
         if (modal) modal.style.display = 'none';
         this.resetForm();
-        this.editingUrl = null;
+        this.editingId = null;
     }
 
     resetForm() {
@@ -403,6 +831,7 @@ class AtodeApp {
         } else {
             tagsInput.value = tagName;
         }
+        this.updateRelatedTags();
     }
 
     addToSiteSearch(siteName) {
@@ -422,11 +851,9 @@ class AtodeApp {
             existingSuggestions.remove();
         }
 
-        if (this.popularTags.length === 0) return;
-
         const suggestionsDiv = document.createElement('div');
         suggestionsDiv.className = 'tag-suggestions';
-        suggestionsDiv.innerHTML = `
+        suggestionsDiv.innerHTML = this.popularTags.length === 0 ? '<div class="related-tags"></div>' : `
             <label>よく使うタグ（クリックで自動入力）:</label>
             <div class="suggestion-tags">
                 ${this.popularTags.map(tagCount => 
@@ -435,9 +862,49 @@ class AtodeApp {
                 </span>`
             ).join('')}
         </div>
+        <div class="related-tags"></div>
     `;
 
         tagsInput.parentNode.insertBefore(suggestionsDiv, tagsInput.nextSibling);
+        this.updateRelatedTags();
+    }
+
+    // 入力済みのタグと一緒に付いていることの多いタグを候補に出す
+    async updateRelatedTags() {
+        const tagsInput = document.getElementById('tagsInput');
+        const container = tagsInput?.parentNode?.querySelector('.related-tags');
+        if (!container) return;
+
+        const entered = tagsInput.value.split(',').map(t => normalizeTagString(t)).filter(Boolean);
+        if (entered.length === 0) {
+            container.innerHTML = '';
+            return;
+        }
+
+        try {
+            const counts = new Map();
+            for (const tag of entered.slice(-3)) {
+                const related = await invoke('get_related_tags', { tag, limit: 10 });
+                related.forEach(r => counts.set(r.tag, (counts.get(r.tag) || 0) + r.count));
+            }
+            const candidates = [...counts.entries()]
+                .filter(([tag]) => !entered.includes(tag))
+                .sort((a, b) => b[1] - a[1] || a[0].localeCompare(b[0]))
+                .slice(0, 8);
+
+            container.innerHTML = candidates.length === 0 ? '' : `
+                <label>関連タグ（一緒に付いていることが多いタグ）:</label>
+                <div class="suggestion-tags">
+                    ${candidates.map(([tag]) =>
+                        `<span class="tag suggestion-tag" onclick="app.handleTagClick('${this.escapeHtml(tag)}', 'input')" title="クリックで追加">
+                        ${this.escapeHtml(tag)}
+                    </span>`
+                    ).join('')}
+                </div>
+            `;
+        } catch (error) {
+            console.error('関連タグ取得エラー:', error);
+        }
     }
 
     showError(message) {
@@ -488,6 +955,13 @@ class AtodeApp {
         }, 3000);
     }
 
+    // キーワード検索の一致箇所（\u0002〜\u0003で囲まれる）を<mark>で強調
+    renderSnippet(snippet) {
+        return this.escapeHtml(snippet)
+            .replace(/\u0002/g, '<mark>')
+            .replace(/\u0003/g, '</mark>');
+    }
+
     escapeHtml(text) {
         const div = document.createElement('div');
         div.textContent = text;